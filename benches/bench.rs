@@ -66,20 +66,259 @@ fn bench_murmur3_128(b: &mut Criterion, size: usize) {
     group.finish();
 }
 
+fn bench_murmur3_tiny_keys(b: &mut Criterion) {
+    let mut group = b.benchmark_group("Murmur3_x64_128_tiny_keys");
+    let mut rng = rand::thread_rng();
+    // Keys of hash table workloads are mostly short, lengths are drawn from
+    // a triangular distribution over 1..=15 that peaks at 8.
+    let keys: Vec<Vec<u8>> = (0..1024)
+        .map(|_| {
+            let len = rng.gen_range(1..=8) + rng.gen_range(0..=7);
+            let mut key = vec![0; len];
+            rng.fill_bytes(&mut key);
+            key
+        })
+        .collect();
+
+    group.throughput(Throughput::Elements(keys.len() as u64));
+    group.bench_with_input("rust-func", &keys, |b, keys| {
+        b.iter(|| {
+            for k in keys {
+                black_box(murmurhash3_x64_128(k, 0));
+            }
+        })
+    });
+    group.bench_with_input("c-func", &keys, |b, keys| {
+        b.iter(|| {
+            for k in keys {
+                black_box(hash128_64(k, 0));
+            }
+        })
+    });
+    group.bench_with_input("hasher", &keys, |b, keys| {
+        b.iter(|| {
+            for k in keys {
+                let mut hasher = Hasher128::with_seed(0);
+                hasher.write(k);
+                black_box(hasher.finish128());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_murmur3_large(b: &mut Criterion) {
+    let mut group = b.benchmark_group("Murmur3_x64_128_large");
+    // Compare the results of running with and without `--features perf`.
+    let name = if cfg!(feature = "perf") {
+        "prefetch"
+    } else {
+        "no-prefetch"
+    };
+    for p in [13, 16] {
+        let size = 2usize.pow(p);
+        let mut buf = vec![0; size];
+        rand::thread_rng().fill_bytes(buf.as_mut_slice());
+
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::new(name, size), &buf, |b, i| {
+            b.iter(|| {
+                let res = murmurhash3_x64_128(i, 0);
+                black_box(res);
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("hasher", size), &buf, |b, i| {
+            b.iter(|| {
+                let mut hasher = Hasher128::with_seed(0);
+                hasher.write(i);
+                let res = hasher.finish128();
+                black_box(res);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+// Run with `--features std`.
+#[cfg(feature = "std")]
+fn bench_murmur3_mmap(b: &mut Criterion) {
+    let mut group = b.benchmark_group("Murmur3_x64_128_mmap");
+    // A large buffer simulating a mapped file, hashed at an offset of 0
+    // and 1 to cover both aligned and unaligned block reads.
+    let size = 16 << 20;
+    let mut bytes = vec![0; size + 1];
+    rand::thread_rng().fill_bytes(bytes.as_mut_slice());
+
+    group.throughput(Throughput::Bytes(size as u64));
+    for offset in [0, 1] {
+        let input = &bytes[offset..offset + size];
+        group.bench_with_input(BenchmarkId::new("rust-func", offset), input, |b, i| {
+            b.iter(|| black_box(murmurhash3_x64_128(i, 0)))
+        });
+        group.bench_with_input(BenchmarkId::new("mmap", offset), input, |b, i| {
+            b.iter(|| black_box(murmurhash3_x64_128_mmap(i, 0)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_murmur3_stream(b: &mut Criterion) {
+    let mut group = b.benchmark_group("Murmur3_x64_128_stream");
+    // Many 4 KiB chunks, like the buffers of a decoder, written at an
+    // aligned and an unaligned address.
+    let chunk = 4096;
+    let count = 256;
+    let mut buf = vec![0; chunk * count + 16];
+    rand::thread_rng().fill_bytes(buf.as_mut_slice());
+    let aligned = buf.as_ptr().align_offset(16);
+
+    group.throughput(Throughput::Bytes((chunk * count) as u64));
+    for (name, offset) in [("aligned", aligned), ("unaligned", aligned + 1)] {
+        let input = &buf[offset..offset + chunk * count];
+        group.bench_with_input(BenchmarkId::new("hasher", name), input, |b, i| {
+            b.iter(|| {
+                let mut hasher = Hasher128::with_seed(0);
+                for c in i.chunks(chunk) {
+                    hasher.write(c);
+                }
+                black_box(hasher.finish128())
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_murmur3_feed(b: &mut Criterion) {
+    let mut group = b.benchmark_group("Murmur3_feed");
+    // Blocks are derived from a counter in registers, so the mixing is
+    // measured without memory access.
+    let blocks = 1024u64;
+
+    group.throughput(Throughput::Bytes(blocks * 16));
+    group.bench_function(BenchmarkId::new("feed128", blocks), |b| {
+        b.iter(|| {
+            let (mut h1, mut h2) = black_box((0, 0));
+            for i in 0..blocks {
+                let res = raw::feed128(h1, h2, i, !i);
+                h1 = res.0;
+                h2 = res.1;
+            }
+            black_box((h1, h2))
+        })
+    });
+    group.throughput(Throughput::Bytes(blocks * 4));
+    group.bench_function(BenchmarkId::new("feed32", blocks), |b| {
+        b.iter(|| {
+            let mut h = black_box(0);
+            for i in 0..blocks as u32 {
+                h = raw::feed32(h, i);
+            }
+            black_box(h)
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_murmur3_inline(b: &mut Criterion) {
+    let mut group = b.benchmark_group("Murmur3_inline");
+    // Compare the results of running with and without
+    // `--features codegen-inline`.
+    let inline = if cfg!(feature = "codegen-inline") {
+        "always"
+    } else {
+        "hint"
+    };
+    for size in [64, 1024] {
+        let mut buf = vec![0; size];
+        rand::thread_rng().fill_bytes(buf.as_mut_slice());
+
+        group.throughput(Throughput::Bytes(size as u64));
+        let id = format!("x64_128-func-{}", inline);
+        group.bench_with_input(BenchmarkId::new(id, size), &buf, |b, i| {
+            b.iter(|| black_box(murmurhash3_x64_128(i, 0)))
+        });
+        let id = format!("x64_128-hasher-{}", inline);
+        group.bench_with_input(BenchmarkId::new(id, size), &buf, |b, i| {
+            b.iter(|| {
+                let mut hasher = Hasher128::with_seed(0);
+                hasher.write(i);
+                black_box(hasher.finish128())
+            })
+        });
+        let id = format!("x86_32-func-{}", inline);
+        group.bench_with_input(BenchmarkId::new(id, size), &buf, |b, i| {
+            b.iter(|| black_box(murmurhash3_x86_32(i, 0)))
+        });
+        let id = format!("x86_32-hasher-{}", inline);
+        group.bench_with_input(BenchmarkId::new(id, size), &buf, |b, i| {
+            b.iter(|| {
+                let mut hasher = Hasher32::with_seed(0);
+                hasher.write(i);
+                black_box(hasher.finish32())
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_murmur3_fixed_n<const N: usize>(group: &mut BenchmarkGroup<measurement::WallTime>) {
+    let mut key = [0; N];
+    rand::thread_rng().fill_bytes(&mut key);
+
+    group.throughput(Throughput::Bytes(N as u64));
+    group.bench_with_input(BenchmarkId::new("rust-func", N), &key, |b, i| {
+        b.iter(|| {
+            let res = murmurhash3_x64_128(black_box(i), 0);
+            black_box(res);
+        })
+    });
+    group.bench_with_input(BenchmarkId::new("fixed", N), &key, |b, i| {
+        b.iter(|| {
+            let res = murmurhash3_x64_128_fixed(black_box(i), 0);
+            black_box(res);
+        })
+    });
+}
+
+fn bench_murmur3_fixed(b: &mut Criterion) {
+    let mut group = b.benchmark_group("Murmur3_x64_128_fixed");
+    bench_murmur3_fixed_n::<8>(&mut group);
+    bench_murmur3_fixed_n::<16>(&mut group);
+    bench_murmur3_fixed_n::<20>(&mut group);
+    bench_murmur3_fixed_n::<32>(&mut group);
+    group.finish();
+}
+
 fn bench_murmur3(b: &mut Criterion) {
     for size in 0..=4 {
         bench_murmur3_32(b, size);
     }
 
+    // Every size of the tail is reported individually to show the fixed
+    // per-call overhead for tiny keys.
     for size in 0..=16 {
         bench_murmur3_128(b, size);
     }
+    bench_murmur3_tiny_keys(b);
+    bench_murmur3_fixed(b);
 
     for p in 5..=13 {
         let size = 2usize.pow(p);
         bench_murmur3_32(b, size);
         bench_murmur3_128(b, size);
     }
+    bench_murmur3_large(b);
+    bench_murmur3_stream(b);
+    bench_murmur3_feed(b);
+    bench_murmur3_inline(b);
+    #[cfg(feature = "std")]
+    bench_murmur3_mmap(b);
 }
 
 criterion_group!(benches, bench_murmur3);
@@ -0,0 +1,62 @@
+//! Compile-time MurmurHash3 of literals.
+//!
+//! `murmur32!` expands to the 32-bit MurmurHash3 sum of a literal as a
+//! `u32` literal, so it can be used anywhere a literal can, including
+//! match patterns:
+//! ```
+//! use mur3_macros::murmur32;
+//!
+//! fn command(name: &str) -> Option<u8> {
+//!     match mur3::murmurhash3_x86_32(name.as_bytes(), 0) {
+//!         murmur32!("get") => Some(1),
+//!         murmur32!("set") => Some(2),
+//!         _ => None,
+//!     }
+//! }
+//! assert_eq!(command("set"), Some(2));
+//! ```
+
+#![deny(missing_docs)]
+
+use proc_macro::{Literal, TokenStream, TokenTree};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Lit, LitInt, Token};
+
+struct Input {
+    bytes: Vec<u8>,
+    seed: u32,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Input> {
+        let bytes = match input.parse()? {
+            Lit::Str(s) => s.value().into_bytes(),
+            Lit::ByteStr(s) => s.value(),
+            lit => return Err(syn::Error::new(lit.span(), "expect a string literal")),
+        };
+        let mut seed = 0;
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            seed = input.parse::<LitInt>()?.base10_parse()?;
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Input { bytes, seed })
+    }
+}
+
+/// Calculates the 32-bit MurmurHash3 sum of a literal at compile time.
+///
+/// It accepts a string or byte string literal and an optional seed,
+/// which is 0 by default. The result is the same as
+/// `mur3::murmurhash3_x86_32` of the bytes of the literal.
+/// ```
+/// use mur3_macros::murmur32;
+///
+/// assert_eq!(murmur32!("hello"), 0x248bfa47);
+/// assert_eq!(murmur32!(b"hello", 1), 0xbb4abcad);
+/// ```
+#[proc_macro]
+pub fn murmur32(input: TokenStream) -> TokenStream {
+    let Input { bytes, seed } = parse_macro_input!(input as Input);
+    let h = mur3::murmurhash3_x86_32(&bytes, seed);
+    TokenTree::Literal(Literal::u32_suffixed(h)).into()
+}
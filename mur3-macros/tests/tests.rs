@@ -0,0 +1,25 @@
+use mur3::murmurhash3_x86_32;
+use mur3_macros::murmur32;
+
+#[test]
+fn test_murmur32() {
+    assert_eq!(murmur32!("hello"), murmurhash3_x86_32(b"hello", 0));
+    assert_eq!(murmur32!(""), murmurhash3_x86_32(b"", 0));
+    assert_eq!(murmur32!("hello", 42), murmurhash3_x86_32(b"hello", 42));
+    assert_eq!(
+        murmur32!(b"hello\0world", 1),
+        murmurhash3_x86_32(b"hello\0world", 1)
+    );
+    assert_eq!(
+        murmur32!("\u{2764}\n"),
+        murmurhash3_x86_32("\u{2764}\n".as_bytes(), 0)
+    );
+
+    let h = murmurhash3_x86_32(b"hello, world", 0);
+    let matched = match h {
+        murmur32!("hello") => "hello",
+        murmur32!("hello, world") => "hello, world",
+        _ => "none",
+    };
+    assert_eq!(matched, "hello, world");
+}
@@ -0,0 +1,88 @@
+//! Backends of the block loop of the 128-bit hash.
+//!
+//! `murmurhash3_x64_128` and `Hasher128` feed blocks through the backend
+//! returned by `detect`. Only the scalar backend exists for now, the trait
+//! is the extension point for SIMD backends selected at runtime, which
+//! must produce exactly the same results as `Scalar`.
+//!
+//! ```
+//! use mur3::backend::{self, Backend};
+//!
+//! let h = backend::detect().hash128(b"hello", 0);
+//! assert_eq!(h, backend::Scalar.hash128(b"hello", 0));
+//! assert_eq!(h, mur3::murmurhash3_x64_128(b"hello", 0));
+//! ```
+
+use crate::hash128::{feed_blocks, finish_tail128};
+use crate::sealed::Sealed;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// An implementation of the block loop of the 128-bit hash.
+///
+/// The trait is sealed and can't be implemented outside of the crate, so
+/// methods can be added along with new backends.
+pub trait Backend: Sync + Sealed {
+    /// Gets the name of the backend, for diagnostics.
+    fn name(&self) -> &'static str;
+
+    /// Feeds all complete 16-byte blocks of `bytes` to the state
+    /// `(h1, h2)`, the tail is left to the caller.
+    fn hash128_blocks(&self, bytes: &[u8], h1: u64, h2: u64) -> (u64, u64);
+
+    /// Gets the 128-bit MurmurHash3 sum of data with the backend.
+    ///
+    /// It's the same as `murmurhash3_x64_128` for all backends.
+    fn hash128(&self, bytes: &[u8], seed: u32) -> (u64, u64) {
+        let (h1, h2) = self.hash128_blocks(bytes, seed as u64, seed as u64);
+        let nblocks = bytes.len() / 16;
+        finish_tail128(&bytes[nblocks * 16..], bytes.len() as u64, h1, h2)
+    }
+}
+
+/// The portable backend, which mixes a block at a time.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Scalar;
+
+impl Sealed for Scalar {}
+
+impl Backend for Scalar {
+    fn name(&self) -> &'static str {
+        "scalar"
+    }
+
+    #[inline]
+    fn hash128_blocks(&self, bytes: &[u8], h1: u64, h2: u64) -> (u64, u64) {
+        feed_blocks(bytes, h1, h2)
+    }
+}
+
+/// All backends, in order of preference.
+static BACKENDS: [&dyn Backend; 1] = [&Scalar];
+
+/// One more than the index of the detected backend in `BACKENDS`, 0 if
+/// the CPU hasn't been probed yet.
+static DETECTED: AtomicUsize = AtomicUsize::new(0);
+
+/// Gets the fastest backend supported by the running CPU.
+///
+/// It's always `Scalar` for now. The CPU is only probed by the first call,
+/// later calls load the cached result.
+#[inline]
+pub fn detect() -> &'static dyn Backend {
+    match DETECTED.load(Ordering::Relaxed) {
+        0 => {
+            let i = probe();
+            // Racing probes find the same backend, so relaxed is enough.
+            DETECTED.store(i + 1, Ordering::Relaxed);
+            BACKENDS[i]
+        }
+        i => BACKENDS[i - 1],
+    }
+}
+
+/// Finds the index of the first backend in `BACKENDS` that the running
+/// CPU supports.
+#[cold]
+fn probe() -> usize {
+    0
+}
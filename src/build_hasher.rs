@@ -0,0 +1,43 @@
+use crate::Hasher128;
+use core::hash::BuildHasher;
+
+/// A `BuildHasher` that creates `Hasher128`s with a fixed seed.
+///
+/// It works with any map that takes a `BuildHasher`, like
+/// `std::collections::HashMap`, `hashbrown::HashMap` and
+/// `indexmap::IndexMap`. The default seed is 0.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// let mut map = HashMap::with_hasher(mur3::Murmur3BuildHasher128::with_seed(42));
+/// map.insert("hello", 1);
+/// assert_eq!(map["hello"], 1);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Murmur3BuildHasher128 {
+    seed: u32,
+}
+
+impl Murmur3BuildHasher128 {
+    /// Creates a builder of hashers with given seed.
+    #[inline]
+    pub fn with_seed(seed: u32) -> Murmur3BuildHasher128 {
+        Murmur3BuildHasher128 { seed }
+    }
+
+    /// Gets the seed of built hashers.
+    #[inline]
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+impl BuildHasher for Murmur3BuildHasher128 {
+    type Hasher = Hasher128;
+
+    #[inline]
+    fn build_hasher(&self) -> Hasher128 {
+        Hasher128::with_seed(self.seed)
+    }
+}
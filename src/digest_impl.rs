@@ -0,0 +1,122 @@
+use crate::{Hasher128, Hasher32};
+use core::hash::Hasher;
+use digest::consts::{U16, U4};
+use digest::{FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update};
+
+/// The 128-bit MurmurHash3 as a `digest::Digest`.
+///
+/// The output is the same as `Hasher128::finish_bytes`. `Default` uses
+/// seed 0, use `with_seed` for other seeds. Note MurmurHash3 is not a
+/// cryptographic hash.
+#[allow(non_camel_case_types)]
+#[derive(Clone)]
+pub struct Murmur3_128 {
+    seed: u32,
+    hasher: Hasher128,
+}
+
+impl Murmur3_128 {
+    /// Creates a digest with given seed.
+    pub fn with_seed(seed: u32) -> Murmur3_128 {
+        Murmur3_128 {
+            seed,
+            hasher: Hasher128::with_seed(seed),
+        }
+    }
+}
+
+impl Default for Murmur3_128 {
+    fn default() -> Murmur3_128 {
+        Murmur3_128::with_seed(0)
+    }
+}
+
+impl HashMarker for Murmur3_128 {}
+
+impl OutputSizeUser for Murmur3_128 {
+    type OutputSize = U16;
+}
+
+impl Update for Murmur3_128 {
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.write(data);
+    }
+}
+
+impl FixedOutput for Murmur3_128 {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.hasher.finish_bytes());
+    }
+}
+
+impl FixedOutputReset for Murmur3_128 {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.hasher.finish_bytes());
+        Reset::reset(self);
+    }
+}
+
+impl Reset for Murmur3_128 {
+    fn reset(&mut self) {
+        self.hasher.reset_with_seed(self.seed);
+    }
+}
+
+/// The 32-bit MurmurHash3 as a `digest::Digest`.
+///
+/// The output is the little endian bytes of `Hasher32::finish32`.
+/// `Default` uses seed 0, use `with_seed` for other seeds. Note
+/// MurmurHash3 is not a cryptographic hash.
+#[allow(non_camel_case_types)]
+#[derive(Clone)]
+pub struct Murmur3_32 {
+    seed: u32,
+    hasher: Hasher32,
+}
+
+impl Murmur3_32 {
+    /// Creates a digest with given seed.
+    pub fn with_seed(seed: u32) -> Murmur3_32 {
+        Murmur3_32 {
+            seed,
+            hasher: Hasher32::with_seed(seed),
+        }
+    }
+}
+
+impl Default for Murmur3_32 {
+    fn default() -> Murmur3_32 {
+        Murmur3_32::with_seed(0)
+    }
+}
+
+impl HashMarker for Murmur3_32 {}
+
+impl OutputSizeUser for Murmur3_32 {
+    type OutputSize = U4;
+}
+
+impl Update for Murmur3_32 {
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.write(data);
+    }
+}
+
+impl FixedOutput for Murmur3_32 {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.hasher.finish32().to_le_bytes());
+    }
+}
+
+impl FixedOutputReset for Murmur3_32 {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.hasher.finish32().to_le_bytes());
+        Reset::reset(self);
+    }
+}
+
+impl Reset for Murmur3_32 {
+    fn reset(&mut self) {
+        self.hasher = Hasher32::with_seed(self.seed);
+    }
+}
@@ -0,0 +1,67 @@
+//! C ABI of the hash functions.
+//!
+//! The exported functions share the signatures of the reference C
+//! implementation, with a `mur3_` prefix to avoid conflicts:
+//! ```c
+//! void mur3_murmurhash3_x86_32(const void *key, int len, uint32_t seed, void *out);
+//! void mur3_murmurhash3_x64_128(const void *key, int len, uint32_t seed, void *out);
+//! ```
+//!
+//! To build a library that can be linked by C/C++ projects, use
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or
+//! `staticlib`).
+
+use core::ffi::{c_int, c_void};
+use core::slice;
+
+/// # Safety
+///
+/// `key` must be valid for reading `len` bytes during `'a` if `len` is
+/// positive.
+unsafe fn as_bytes<'a>(key: *const c_void, len: c_int) -> &'a [u8] {
+    if len <= 0 {
+        return &[];
+    }
+    // SAFETY: guaranteed by the caller.
+    slice::from_raw_parts(key as *const u8, len as usize)
+}
+
+/// Calculates the 32-bit MurmurHash3 sum of `len` bytes at `key`, and
+/// writes it to `out` as a native endian `uint32_t`.
+///
+/// # Safety
+///
+/// `key` must be valid for reading `len` bytes, and `out` must be valid
+/// for writing 4 bytes. Both don't need to be aligned.
+#[no_mangle]
+pub unsafe extern "C" fn mur3_murmurhash3_x86_32(
+    key: *const c_void,
+    len: c_int,
+    seed: u32,
+    out: *mut c_void,
+) {
+    let h = crate::murmurhash3_x86_32(as_bytes(key, len), seed);
+    // SAFETY: `out` is valid for writing 4 bytes as documented.
+    (out as *mut u32).write_unaligned(h);
+}
+
+/// Calculates the 128-bit MurmurHash3 sum of `len` bytes at `key`, and
+/// writes it to `out` as two native endian `uint64_t`.
+///
+/// # Safety
+///
+/// `key` must be valid for reading `len` bytes, and `out` must be valid
+/// for writing 16 bytes. Both don't need to be aligned.
+#[no_mangle]
+pub unsafe extern "C" fn mur3_murmurhash3_x64_128(
+    key: *const c_void,
+    len: c_int,
+    seed: u32,
+    out: *mut c_void,
+) {
+    let (h1, h2) = crate::murmurhash3_x64_128(as_bytes(key, len), seed);
+    // SAFETY: `out` is valid for writing 16 bytes as documented.
+    let out = out as *mut u64;
+    out.write_unaligned(h1);
+    out.add(1).write_unaligned(h2);
+}
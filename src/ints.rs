@@ -0,0 +1,53 @@
+use crate::Hasher128;
+use core::hash::Hasher;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Integer primitives that can be hashed as little endian bytes.
+///
+/// It's implemented for all fixed width integers. `usize` and `isize` are
+/// excluded on purpose, their widths differ across platforms. The trait is
+/// sealed and can't be implemented outside of the crate.
+pub trait ToLeBytes: sealed::Sealed + Copy {
+    /// Feeds the little endian bytes of the value to the hasher.
+    fn write_le(self, hasher: &mut Hasher128);
+}
+
+macro_rules! impl_to_le_bytes {
+    ($($t:ty),*) => {$(
+        impl sealed::Sealed for $t {}
+
+        impl ToLeBytes for $t {
+            #[inline]
+            fn write_le(self, hasher: &mut Hasher128) {
+                hasher.write(&self.to_le_bytes());
+            }
+        }
+    )*};
+}
+
+impl_to_le_bytes!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Gets the 128-bit MurmurHash3 sum of integers.
+///
+/// Every integer is fed as little endian bytes, so the result is the same
+/// on all platforms, unlike hashing the memory of the slice, which depends
+/// on the endianness.
+///
+/// ```
+/// let scores: &[i64] = &[3, -1, 4];
+/// let bytes: Vec<u8> = scores.iter().flat_map(|s| s.to_le_bytes()).collect();
+/// assert_eq!(
+///     mur3::murmurhash3_x64_128_ints(scores, 0),
+///     mur3::murmurhash3_x64_128(&bytes, 0)
+/// );
+/// ```
+pub fn murmurhash3_x64_128_ints<T: ToLeBytes>(ints: &[T], seed: u32) -> (u64, u64) {
+    let mut hasher = Hasher128::with_seed(seed);
+    for &i in ints {
+        i.write_le(&mut hasher);
+    }
+    hasher.finish128()
+}
@@ -31,40 +31,35 @@
 //! ```
 //!
 //! The library can be used in `no_std` freely.
+//!
+//! By default, blocks are read with unaligned pointer reads. With `safe`
+//! feature, the crate is built without unsafe code, except the `ffi`
+//! module, and the results are exactly the same.
 
 #![no_std]
 #![deny(missing_docs)]
+#![cfg_attr(feature = "safe", deny(unsafe_code))]
 
-macro_rules! read_n_val {
-    ($name:ident, $next:ident, $n:expr) => {
-        #[inline]
-        fn $name(res: &mut u64, p: *const u8) {
-            *res |= unsafe { (*p.add($n)) as u64 } << (8 * $n);
-            $next(res, p);
-        }
-    };
-}
-
-read_n_val!(read_7, read_6, 6);
-read_n_val!(read_6, read_5, 5);
-read_n_val!(read_5, read_4, 4);
-
-#[inline]
-fn read_4(res: &mut u64, p: *const u8) {
-    *res |= unsafe { u32::from_le((p as *const u32).read_unaligned()) as u64 }
-}
-
-read_n_val!(read_3, read_2, 2);
-read_n_val!(read_2, read_1, 1);
-
-#[inline]
-fn read_1(res: &mut u64, p: *const u8) {
-    *res |= unsafe { *p as u64 };
-}
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod hash128 {
+    use crate::backend;
+    #[cfg(feature = "alloc")]
+    use alloc::{
+        string::{String, ToString},
+        vec::Vec,
+    };
+    use core::convert::TryInto;
+    use core::fmt::{self, Write as _};
+    use core::hash::{Hash, Hasher};
+    use core::num::NonZeroU32;
+    #[cfg(not(any(target_arch = "wasm32", feature = "portable", feature = "safe")))]
     use core::ptr;
-    use core::{hash::Hasher, slice};
+    #[cfg(all(target_endian = "little", not(feature = "safe")))]
+    use core::slice;
 
     const C1: u64 = 0x87c37b91114253d5;
     const C2: u64 = 0x4cf5ad432745937f;
@@ -76,40 +71,897 @@ mod hash128 {
     /// If you only need 64-bit result, just use the first returned value.
     /// To feed multiple byte slices, use `Hasher128` instead.
     ///
-    /// The function is optimized for 64 bit platform.
+    /// The function is optimized for 64 bit platform. On 32 bit platforms,
+    /// like i686, armv7 and wasm32, 64 bit multiplications and rotations
+    /// are emulated with pairs of 32 bit instructions, which only makes it
+    /// slower, the result is the same on all platforms.
     pub fn murmurhash3_x64_128(bytes: &[u8], seed: u32) -> (u64, u64) {
-        let nblocks = bytes.len() / 16;
+        hash128(bytes, seed)
+    }
 
-        let mut h1 = seed as u64;
-        let mut h2 = seed as u64;
+    /// Feeds block aligned data to a 128-bit MurmurHash3 state and
+    /// returns the new state.
+    ///
+    /// It's a low level primitive for chaining non-contiguous regions
+    /// without a `Hasher128`. The initial state is `(seed as u64, seed as
+    /// u64)` with a `consumed_len` of 0. `consumed_len` is the number of
+    /// bytes already fed into `(h1, h2)`; the caller has to keep tracking
+    /// the total length and finish the state with
+    /// `murmurhash3_x64_128_finish`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `bytes` or `consumed_len` is not a multiple
+    /// of 16. `bytes` must consist of whole blocks, a partial block is never
+    /// silently dropped.
+    ///
+    /// ```
+    /// let data = [7u8; 40];
+    /// let (h1, h2) = mur3::murmurhash3_x64_128_resume(&data[..16], 42, 42, 0);
+    /// let (h1, h2) = mur3::murmurhash3_x64_128_resume(&data[16..32], h1, h2, 16);
+    /// let h = mur3::murmurhash3_x64_128_finish(&data[32..], 40, h1, h2);
+    /// assert_eq!(h, mur3::murmurhash3_x64_128(&data, 42));
+    /// ```
+    // `is_multiple_of` is only stable since Rust 1.87.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn murmurhash3_x64_128_resume(
+        bytes: &[u8],
+        h1: u64,
+        h2: u64,
+        consumed_len: u64,
+    ) -> (u64, u64) {
+        assert!(
+            bytes.len() % 16 == 0,
+            "data length {} is not a multiple of 16",
+            bytes.len()
+        );
+        assert!(
+            consumed_len % 16 == 0,
+            "consumed length {} is not a multiple of 16",
+            consumed_len
+        );
+        feed_blocks(bytes, h1, h2)
+    }
+
+    /// Finishes a 128-bit MurmurHash3 state with the tail of data.
+    ///
+    /// `tail` is the remaining data less than a block, and `total_len` is
+    /// the length of all data including the tail. See
+    /// `murmurhash3_x64_128_resume`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tail` is not shorter than 16 bytes.
+    pub fn murmurhash3_x64_128_finish(tail: &[u8], total_len: u64, h1: u64, h2: u64) -> (u64, u64) {
+        assert!(
+            tail.len() < 16,
+            "tail length {} is not less than 16",
+            tail.len()
+        );
+        finish_tail128(tail, total_len, h1, h2)
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of data with a non-zero seed.
+    ///
+    /// It's the same as `murmurhash3_x64_128(bytes, seed.get())`, for
+    /// code that treats seed 0 as unseeded.
+    #[inline]
+    pub fn murmurhash3_x64_128_nonzero(bytes: &[u8], seed: NonZeroU32) -> (u64, u64) {
+        murmurhash3_x64_128(bytes, seed.get())
+    }
 
+    /// Checks whether the 128-bit MurmurHash3 sum of data is `expected`.
+    ///
+    /// It's the same as `murmurhash3_x64_128(bytes, seed) == expected`,
+    /// for lookup tables that store the hash of every key. With `ct`
+    /// feature, both halves are always compared without branching on the
+    /// first one. Like `ct` for the tail, it's not a security guarantee.
+    #[inline]
+    pub fn murmurhash3_x64_128_eq(bytes: &[u8], seed: u32, expected: (u64, u64)) -> bool {
+        let (h1, h2) = murmurhash3_x64_128(bytes, seed);
+        #[cfg(not(feature = "ct"))]
+        {
+            (h1, h2) == expected
+        }
+        #[cfg(feature = "ct")]
+        {
+            ((h1 ^ expected.0) | (h2 ^ expected.1)) == 0
+        }
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of anything that can be viewed as
+    /// bytes, like `String`, `Vec<u8>` and byte arrays.
+    ///
+    /// It's the same as `murmurhash3_x64_128(b.as_ref(), seed)`.
+    #[inline]
+    pub fn murmurhash3_x64_128_of<B: AsRef<[u8]>>(b: B, seed: u32) -> (u64, u64) {
+        murmurhash3_x64_128(b.as_ref(), seed)
+    }
+
+    /// Gets the first 64 bits of the 128-bit MurmurHash3 sum of data.
+    ///
+    /// It's the same as `murmurhash3_x64_128(bytes, seed).0`. Note it's
+    /// not cheaper than the 128-bit function, as the first half depends on
+    /// the second half in every round.
+    #[inline]
+    pub fn murmurhash3_x64_64(bytes: &[u8], seed: u32) -> u64 {
+        murmurhash3_x64_128(bytes, seed).0
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of a key of fixed length.
+    ///
+    /// The numbers of blocks and tail bytes are constants of `N`, and the
+    /// blocks are fed by the scalar loop directly instead of the detected
+    /// backend, so the optimizer can unroll the loop and pick the tail
+    /// path at compile time, which is faster than `murmurhash3_x64_128` for
+    /// small keys. The result is the same as
+    /// `murmurhash3_x64_128(&key[..], seed)`.
+    #[inline]
+    pub fn murmurhash3_x64_128_fixed<const N: usize>(key: &[u8; N], seed: u32) -> (u64, u64) {
+        let (blocks, tail) = key.split_at(N / 16 * 16);
+        let (h1, h2) = feed_blocks(blocks, seed as u64, seed as u64);
+        finish_tail128(tail, N as u64, h1, h2)
+    }
+
+    #[inline(always)]
+    fn hash128(bytes: &[u8], seed: u32) -> (u64, u64) {
+        // Small keys, including empty ones, are all tail, skip the setup
+        // of the block loop.
+        if bytes.len() < 16 {
+            return finish_tail128(bytes, bytes.len() as u64, seed as u64, seed as u64);
+        }
+        let (h1, h2) = backend::detect().hash128_blocks(bytes, seed as u64, seed as u64);
+        let nblocks = bytes.len() / 16;
+        finish_tail128(&bytes[nblocks * 16..], bytes.len() as u64, h1, h2)
+    }
+
+    /// Feeds all complete blocks of `bytes` to the state, the tail is
+    /// left to the caller.
+    ///
+    /// It's the block loop shared by the one-shot functions and
+    /// `Hasher128`. The `codegen-inline` feature forces inlining it for
+    /// comparing code generation in benchmarks.
+    #[cfg(not(feature = "safe"))]
+    #[cfg_attr(not(feature = "codegen-inline"), inline)]
+    #[cfg_attr(feature = "codegen-inline", inline(always))]
+    pub(crate) fn feed_blocks(bytes: &[u8], mut h1: u64, mut h2: u64) -> (u64, u64) {
         let mut start = bytes.as_ptr();
-        for _ in 0..nblocks {
+        for i in 0..bytes.len() / 16 {
+            // One prefetch per cache line is enough.
+            if i % 4 == 0 {
+                prefetch(start);
+            }
+            // SAFETY: there are `bytes.len() / 16` blocks, each iteration
+            // reads one block at `start` and advances it by one block, so
+            // both reading and advancing stay within `bytes`.
             let (k1, k2) = unsafe {
-                let k1 = ptr::read_unaligned(start as *const u64);
-                start = start.add(8);
-                let k2 = ptr::read_unaligned(start as *const u64);
-                start = start.add(8);
-                (u64::from_le(k1), u64::from_le(k2))
+                let block = read_block(start);
+                start = start.add(16);
+                block
             };
             let res = feed128(h1, h2, k1, k2);
             h1 = res.0;
             h2 = res.1;
         }
+        (h1, h2)
+    }
+
+    /// Feeds all complete blocks of `bytes` to the state, the tail is
+    /// left to the caller.
+    ///
+    /// With `safe` feature, blocks are read by indexing slices.
+    #[cfg(feature = "safe")]
+    #[cfg_attr(not(feature = "codegen-inline"), inline)]
+    #[cfg_attr(feature = "codegen-inline", inline(always))]
+    pub(crate) fn feed_blocks(bytes: &[u8], mut h1: u64, mut h2: u64) -> (u64, u64) {
+        for block in bytes.chunks_exact(16) {
+            let k1 = u64::from_le_bytes(block[..8].try_into().unwrap());
+            let k2 = u64::from_le_bytes(block[8..].try_into().unwrap());
+            let res = feed128(h1, h2, k1, k2);
+            h1 = res.0;
+            h2 = res.1;
+        }
+        (h1, h2)
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of a string.
+    ///
+    /// It's exactly the same as hashing `s.as_bytes()`.
+    #[inline]
+    pub fn murmurhash3_x64_128_str(s: &str, seed: u32) -> (u64, u64) {
+        murmurhash3_x64_128(s.as_bytes(), seed)
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of 32-bit words in little endian.
+    ///
+    /// It's the same as hashing the little endian bytes of all words
+    /// concatenated, so the result is the same on all platforms.
+    pub fn murmurhash3_x64_128_u32le(data: &[u32], seed: u32) -> (u64, u64) {
+        #[cfg(all(target_endian = "little", not(feature = "safe")))]
+        {
+            // SAFETY: the bytes of `data` are initialized, and `u8` has no
+            // alignment requirement.
+            let bytes =
+                unsafe { slice::from_raw_parts(data.as_ptr() as *const u8, data.len() * 4) };
+            murmurhash3_x64_128(bytes, seed)
+        }
+        #[cfg(any(target_endian = "big", feature = "safe"))]
+        {
+            let mut hasher = Hasher128::with_seed(seed);
+            for w in data {
+                hasher.write(&w.to_le_bytes());
+            }
+            hasher.finish128()
+        }
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of 16-bit words in little endian.
+    ///
+    /// It's the same as hashing the little endian bytes of all words
+    /// concatenated, so the result is the same on all platforms. It's
+    /// useful for hashing UTF-16 text.
+    pub fn murmurhash3_x64_128_u16le(data: &[u16], seed: u32) -> (u64, u64) {
+        #[cfg(all(target_endian = "little", not(feature = "safe")))]
+        {
+            // SAFETY: see `murmurhash3_x64_128_u32le`.
+            let bytes =
+                unsafe { slice::from_raw_parts(data.as_ptr() as *const u8, data.len() * 2) };
+            murmurhash3_x64_128(bytes, seed)
+        }
+        #[cfg(any(target_endian = "big", feature = "safe"))]
+        {
+            let mut hasher = Hasher128::with_seed(seed);
+            for w in data {
+                hasher.write(&w.to_le_bytes());
+            }
+            hasher.finish128()
+        }
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of the `Display` output of a value.
+    ///
+    /// The value is formatted into a `String` before hashing, use
+    /// `hash_display_fmt` to get the same result without allocation.
+    #[cfg(feature = "alloc")]
+    pub fn hash_display<T: fmt::Display + ?Sized>(value: &T, seed: u32) -> (u64, u64) {
+        murmurhash3_x64_128(value.to_string().as_bytes(), seed)
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of the `Display` output of a value
+    /// without allocation.
+    ///
+    /// Formatted fragments are fed to a `Hasher128` directly, so the result
+    /// is the same as `hash_display`.
+    pub fn hash_display_fmt<T: fmt::Display + ?Sized>(value: &T, seed: u32) -> (u64, u64) {
+        let mut hasher = Hasher128::with_seed(seed);
+        write!(hasher, "{}", value)
+            .expect("a Display implementation returned an error unexpectedly");
+        hasher.finish128()
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of a value via its `Hash`
+    /// implementation.
+    ///
+    /// The result depends on how the `Hash` implementation feeds the
+    /// hasher, for example, the field order of a derived implementation,
+    /// and `write_usize` feeds the native width of `usize`, so it differs
+    /// across platforms. Hence it's not suitable as a stable on-disk format.
+    pub fn hash_value128<T: Hash + ?Sized>(value: &T, seed: u32) -> (u64, u64) {
+        let mut hasher = Hasher128::with_seed(seed);
+        value.hash(&mut hasher);
+        hasher.finish128()
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of data, along with whether data
+    /// is aligned to 16-byte blocks.
+    ///
+    /// The flag is `bytes.len() % 16 == 0`, that is no tail needs to be
+    /// processed.
+    // `is_multiple_of` is only stable since Rust 1.87.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn murmurhash3_x64_128_aligned_info(bytes: &[u8], seed: u32) -> ((u64, u64), bool) {
+        (murmurhash3_x64_128(bytes, seed), bytes.len() % 16 == 0)
+    }
+
+    /// Maps the 64-bit MurmurHash3 sum of data into `[0, n)`.
+    ///
+    /// It uses Lemire's fast range reduction `(hash * n) >> 64`, which is
+    /// a multiplication instead of the much slower division of
+    /// `hash % n`. The result is decided by the high bits of the hash.
+    /// Every value in the range is mapped from either `floor(2^64 / n)` or
+    /// `ceil(2^64 / n)` hashes, so the bias is the same as modulo's, but
+    /// the larger buckets are evenly spread out instead of being the
+    /// lowest ones. Returns 0 if `n` is 0.
+    pub fn fast_range(bytes: &[u8], seed: u32, n: u64) -> u64 {
+        let h = murmurhash3_x64_128(bytes, seed).0;
+        ((h as u128 * n as u128) >> 64) as u64
+    }
+
+    /// Derives `k` indices in `[0, m)` from a 128-bit hash for Bloom
+    /// filters.
+    ///
+    /// The i-th index is `h.0.wrapping_add(i * h.1) % m`, which is the
+    /// double hashing scheme of Kirsch and Mitzenmacher, so one 128-bit
+    /// hash is enough for all `k` hash functions of a filter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` is 0.
+    ///
+    /// ```
+    /// let h = mur3::murmurhash3_x64_128(b"hello", 0);
+    /// for i in mur3::bloom_indices(h, 3, 1000) {
+    ///     assert!(i < 1000);
+    /// }
+    /// ```
+    pub fn bloom_indices(h: (u64, u64), k: usize, m: u64) -> impl Iterator<Item = u64> {
+        assert!(m != 0, "the number of bits must not be 0");
+        (0..k as u64).map(move |i| h.0.wrapping_add(i.wrapping_mul(h.1)) % m)
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of the concatenation of chunks.
+    ///
+    /// It's a shortcut of feeding every chunk to a `Hasher128`, the result
+    /// is the same as hashing the concatenated data at once.
+    pub fn murmurhash3_x64_128_iter<'a, I: IntoIterator<Item = &'a [u8]>>(
+        chunks: I,
+        seed: u32,
+    ) -> (u64, u64) {
+        let mut hasher = Hasher128::with_seed(seed);
+        hasher.write_iter(chunks);
+        hasher.finish128()
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of bytes produced by an iterator.
+    ///
+    /// Bytes are buffered 16 at a time, so it doesn't allocate. The result
+    /// is the same as hashing the collected bytes.
+    pub fn murmurhash3_x64_128_from_bytes<I: IntoIterator<Item = u8>>(
+        iter: I,
+        seed: u32,
+    ) -> (u64, u64) {
+        let mut h1 = seed as u64;
+        let mut h2 = seed as u64;
+        let mut block = [0; 16];
+        let mut len = 0;
+        let mut total = 0u64;
+        for b in iter {
+            block[len] = b;
+            len += 1;
+            if len == 16 {
+                let k1 = u64::from_le_bytes(block[..8].try_into().unwrap());
+                let k2 = u64::from_le_bytes(block[8..].try_into().unwrap());
+                let res = feed128(h1, h2, k1, k2);
+                h1 = res.0;
+                h2 = res.1;
+                total = total.wrapping_add(16);
+                len = 0;
+            }
+        }
+        finish_tail128(&block[..len], total.wrapping_add(len as u64), h1, h2)
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of at most the first `max_blocks`
+    /// 16-byte blocks of data.
+    ///
+    /// It hashes a prefix instead of the whole input, which is useful to
+    /// fingerprint huge inputs approximately. The result is the same as
+    /// hashing the truncated prefix directly, so the length mixed in is
+    /// the length of the prefix rather than the whole input. If data is
+    /// shorter than `max_blocks` blocks, the whole data is hashed.
+    pub fn murmurhash3_x64_128_limited(bytes: &[u8], max_blocks: usize, seed: u32) -> (u64, u64) {
+        let len = bytes.len().min(max_blocks.saturating_mul(16));
+        murmurhash3_x64_128(&bytes[..len], seed)
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of scatter-gather buffers.
+    ///
+    /// It's the same as hashing the concatenation of `bufs`, boundaries
+    /// of buffers don't affect the result.
+    pub fn murmurhash3_x64_128_vectored(bufs: &[&[u8]], seed: u32) -> (u64, u64) {
+        let mut hasher = Hasher128::with_seed(seed);
+        for buf in bufs {
+            hasher.write(buf);
+        }
+        hasher.finish128()
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of the two halves of a ring
+    /// buffer.
+    ///
+    /// It's the same as hashing `[first, second].concat()` without
+    /// joining them, for example, the slices returned by
+    /// `VecDeque::as_slices`.
+    ///
+    /// ```
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut ring: VecDeque<u8> = VecDeque::with_capacity(8);
+    /// ring.extend(b"xxxhello");
+    /// ring.drain(..3);
+    /// ring.extend(b" world");
+    /// let (first, second) = ring.as_slices();
+    /// let h = mur3::murmurhash3_x64_128_ring(first, second, 0);
+    /// assert_eq!(h, mur3::murmurhash3_x64_128(b"hello world", 0));
+    /// ```
+    #[inline]
+    pub fn murmurhash3_x64_128_ring(first: &[u8], second: &[u8], seed: u32) -> (u64, u64) {
+        murmurhash3_x64_128_vectored(&[first, second], seed)
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of `IoSlice`s.
+    ///
+    /// It's the same as `murmurhash3_x64_128_vectored`.
+    #[cfg(feature = "std")]
+    pub fn murmurhash3_x64_128_io_slices(bufs: &[std::io::IoSlice<'_>], seed: u32) -> (u64, u64) {
+        let mut hasher = Hasher128::with_seed(seed);
+        for buf in bufs {
+            hasher.write(buf);
+        }
+        hasher.finish128()
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of a path.
+    ///
+    /// The path is hashed as its platform bytes returned by
+    /// `OsStr::as_encoded_bytes`, without any normalization. The bytes
+    /// are raw bytes on Unix but an unspecified encoding of UTF-16 on
+    /// Windows, so the hash of the same path may differ across platforms
+    /// and is only stable on a single platform.
+    #[cfg(feature = "std")]
+    pub fn murmurhash3_x64_128_path(path: &std::path::Path, seed: u32) -> (u64, u64) {
+        murmurhash3_x64_128(path.as_os_str().as_encoded_bytes(), seed)
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of data as a vector of bytes.
+    ///
+    /// The bytes are the same as `Hasher128::finish_bytes`.
+    #[cfg(feature = "alloc")]
+    pub fn murmurhash3_x64_128_vec(bytes: &[u8], seed: u32) -> Vec<u8> {
+        to_bytes(murmurhash3_x64_128(bytes, seed)).to_vec()
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of data as a lowercase hex string.
+    ///
+    /// The string is the hex of the 16 bytes returned by
+    /// `Hasher128::finish_bytes`.
+    #[cfg(feature = "alloc")]
+    pub fn murmurhash3_x64_128_hex(bytes: &[u8], seed: u32) -> String {
+        to_hex(
+            to_bytes(murmurhash3_x64_128(bytes, seed)),
+            b"0123456789abcdef",
+        )
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of data as an uppercase hex string.
+    ///
+    /// The string is the hex of the 16 bytes returned by
+    /// `Hasher128::finish_bytes`.
+    #[cfg(feature = "alloc")]
+    pub fn murmurhash3_x64_128_hex_upper(bytes: &[u8], seed: u32) -> String {
+        to_hex(
+            to_bytes(murmurhash3_x64_128(bytes, seed)),
+            b"0123456789ABCDEF",
+        )
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of data formatted like a UUID.
+    ///
+    /// The 16 bytes returned by `Hasher128::finish_bytes` are formatted as
+    /// lowercase `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`. Note it's not a
+    /// RFC 4122 UUID as no version or variant bits are set, use
+    /// `murmurhash3_x64_128_uuid_v8` if a valid UUID is required.
+    #[cfg(feature = "alloc")]
+    pub fn murmurhash3_x64_128_uuid(bytes: &[u8], seed: u32) -> String {
+        to_uuid(to_bytes(murmurhash3_x64_128(bytes, seed)))
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of data as a version 8 UUID.
+    ///
+    /// It's the same as `murmurhash3_x64_128_uuid` except the version bits
+    /// are set to 8 (custom) and the variant bits are set to the one of
+    /// RFC 4122, so 6 bits of the hash are overwritten.
+    #[cfg(feature = "alloc")]
+    pub fn murmurhash3_x64_128_uuid_v8(bytes: &[u8], seed: u32) -> String {
+        let mut bytes = to_bytes(murmurhash3_x64_128(bytes, seed));
+        bytes[6] = (bytes[6] & 0x0f) | 0x80;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        to_uuid(bytes)
+    }
+
+    /// Pushes the 128-bit MurmurHash3 sums of all suffixes of data to `out`.
+    ///
+    /// `data[i..]` is hashed for every `i` in `0..=data.len()` in order,
+    /// so the last one is the hash of the empty suffix. MurmurHash3 can't
+    /// be calculated incrementally from the right, so every suffix is
+    /// hashed from scratch, which takes O(n^2) time.
+    #[cfg(feature = "alloc")]
+    pub fn suffix_hashes(data: &[u8], seed: u32, out: &mut Vec<(u64, u64)>) {
+        out.reserve(data.len() + 1);
+        for i in 0..=data.len() {
+            out.push(murmurhash3_x64_128(&data[i..], seed));
+        }
+    }
+
+    /// Gets the 128-bit MurmurHash3 sums of all keys.
+    ///
+    /// Use `murmurhash3_x64_128_batch_into` to reuse the output buffer.
+    #[cfg(feature = "alloc")]
+    pub fn murmurhash3_x64_128_batch<K: AsRef<[u8]>>(keys: &[K], seed: u32) -> Vec<(u64, u64)> {
+        let mut out = Vec::new();
+        murmurhash3_x64_128_batch_into(keys, seed, &mut out);
+        out
+    }
+
+    /// Pushes the 128-bit MurmurHash3 sums of all keys to `out`.
+    ///
+    /// Results are appended in order of keys, existing elements of `out`
+    /// are kept, so the caller can reserve the capacity and reuse the
+    /// buffer across batches.
+    #[cfg(feature = "alloc")]
+    pub fn murmurhash3_x64_128_batch_into<K: AsRef<[u8]>>(
+        keys: &[K],
+        seed: u32,
+        out: &mut Vec<(u64, u64)>,
+    ) {
+        out.extend(keys.iter().map(|k| murmurhash3_x64_128(k.as_ref(), seed)));
+    }
+
+    /// Gets the 128-bit MurmurHash3 sums of all keys in parallel.
+    ///
+    /// Keys are hashed on the rayon thread pool, results are in the same
+    /// order as keys and the same as `murmurhash3_x64_128_batch`.
+    #[cfg(feature = "rayon")]
+    pub fn murmurhash3_x64_128_par<K: AsRef<[u8]> + Sync>(
+        keys: &[K],
+        seed: u32,
+    ) -> Vec<(u64, u64)> {
+        use rayon::prelude::*;
+
+        keys.par_iter()
+            .map(|k| murmurhash3_x64_128(k.as_ref(), seed))
+            .collect()
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of a large buffer, like a memory
+    /// mapped file.
+    ///
+    /// The result is the same as `murmurhash3_x64_128`. Data is processed
+    /// in page sized strides, so every page is faulted in and hashed
+    /// before moving on to the next one, blocks are read aligned or
+    /// unaligned depending on the address of the buffer.
+    ///
+    /// Hashing a mapping of a file that is truncated by another process
+    /// while it's being hashed may crash the program with `SIGBUS`, it's
+    /// up to the caller to make sure the file is not modified.
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// // Usually a `memmap2::Mmap`, which derefs to `[u8]`.
+    /// let map = std::fs::read("data.bin")?;
+    /// let (h1, h2) = mur3::murmurhash3_x64_128_mmap(&map, 0);
+    /// # let _ = (h1, h2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn murmurhash3_x64_128_mmap(bytes: &[u8], seed: u32) -> (u64, u64) {
+        const PAGE_SIZE: usize = 4096;
+
+        let (mut h1, mut h2) = (seed as u64, seed as u64);
+        let mut pages = bytes.chunks_exact(PAGE_SIZE);
+        for page in &mut pages {
+            let (n1, n2) = feed_blocks(page, h1, h2);
+            h1 = n1;
+            h2 = n2;
+        }
+        // `feed_blocks` skips the trailing partial block, which is the tail.
+        let rest = pages.remainder();
+        let (h1, h2) = feed_blocks(rest, h1, h2);
+        let tail = &rest[rest.len() / 16 * 16..];
+        finish_tail128(tail, bytes.len() as u64, h1, h2)
+    }
+
+    #[inline]
+    pub(crate) fn to_bytes((h1, h2): (u64, u64)) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&h1.to_le_bytes());
+        bytes[8..].copy_from_slice(&h2.to_le_bytes());
+        bytes
+    }
+
+    #[inline]
+    pub(crate) fn to_bytes_be((h1, h2): (u64, u64)) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&h1.to_be_bytes());
+        bytes[8..].copy_from_slice(&h2.to_be_bytes());
+        bytes
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of data as big endian bytes.
+    ///
+    /// The bytes are the same as `Hasher128::finish_bytes_be`.
+    #[inline]
+    pub fn murmurhash3_x64_128_bytes_be(bytes: &[u8], seed: u32) -> [u8; 16] {
+        to_bytes_be(murmurhash3_x64_128(bytes, seed))
+    }
+
+    /// Formats a 128-bit hash as hex without allocation.
+    ///
+    /// The hex is of the 16 bytes returned by `Hasher128::finish_bytes`,
+    /// the same as `murmurhash3_x64_128_hex`. `Display` and `LowerHex`
+    /// write lowercase digits, `UpperHex` writes uppercase ones.
+    ///
+    /// ```
+    /// let h = mur3::murmurhash3_x64_128(b"hello", 0);
+    /// let s = format!("{}", mur3::Murmur128Hex(h));
+    /// assert_eq!(s, "029bbd41b3a7d8cb191dae486a901e5b");
+    /// ```
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Murmur128Hex(pub (u64, u64));
+
+    impl Murmur128Hex {
+        fn fmt_with(&self, f: &mut fmt::Formatter<'_>, digits: &[u8; 16]) -> fmt::Result {
+            let mut buf = [0; 32];
+            for (i, b) in to_bytes(self.0).iter().enumerate() {
+                buf[i * 2] = digits[(b >> 4) as usize];
+                buf[i * 2 + 1] = digits[(b & 0xf) as usize];
+            }
+            // All digits are ASCII.
+            f.write_str(core::str::from_utf8(&buf).unwrap())
+        }
+    }
+
+    impl fmt::Display for Murmur128Hex {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.fmt_with(f, b"0123456789abcdef")
+        }
+    }
+
+    impl fmt::LowerHex for Murmur128Hex {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.fmt_with(f, b"0123456789abcdef")
+        }
+    }
+
+    impl fmt::UpperHex for Murmur128Hex {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.fmt_with(f, b"0123456789ABCDEF")
+        }
+    }
+
+    /// A 128-bit MurmurHash3 sum, for use as keys of maps and sets.
+    ///
+    /// Sums are ordered by `h1` and then `h2`, the same as `(u64, u64)`.
+    /// `Debug` shows the sum as hex, the same as `Murmur128Hex`. Converting
+    /// to `u128` puts `h1` in the low 64 bits, which matches the byte order
+    /// of `Hasher128::finish_bytes` read as a little endian `u128`.
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut index = BTreeMap::new();
+    /// index.insert(mur3::murmurhash3_x64_128_key(b"hello", 0), "hello");
+    /// let key = mur3::Murmur128::from(mur3::murmurhash3_x64_128(b"hello", 0));
+    /// assert_eq!(index[&key], "hello");
+    /// ```
+    #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Murmur128(pub u64, pub u64);
+
+    impl fmt::Debug for Murmur128 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Murmur128({})", Murmur128Hex((self.0, self.1)))
+        }
+    }
+
+    impl From<(u64, u64)> for Murmur128 {
+        #[inline]
+        fn from((h1, h2): (u64, u64)) -> Murmur128 {
+            Murmur128(h1, h2)
+        }
+    }
+
+    impl From<Murmur128> for (u64, u64) {
+        #[inline]
+        fn from(h: Murmur128) -> (u64, u64) {
+            (h.0, h.1)
+        }
+    }
+
+    impl From<u128> for Murmur128 {
+        #[inline]
+        fn from(h: u128) -> Murmur128 {
+            Murmur128(h as u64, (h >> 64) as u64)
+        }
+    }
+
+    impl From<Murmur128> for u128 {
+        #[inline]
+        fn from(h: Murmur128) -> u128 {
+            (h.1 as u128) << 64 | h.0 as u128
+        }
+    }
+
+    /// Gets the 128-bit MurmurHash3 sum of data as a `Murmur128`.
+    ///
+    /// It's the same as `Murmur128::from(murmurhash3_x64_128(bytes, seed))`.
+    #[inline]
+    pub fn murmurhash3_x64_128_key(bytes: &[u8], seed: u32) -> Murmur128 {
+        Murmur128::from(murmurhash3_x64_128(bytes, seed))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_hex(bytes: [u8; 16], digits: &[u8; 16]) -> String {
+        let mut s = String::with_capacity(32);
+        for b in &bytes {
+            s.push(digits[(b >> 4) as usize] as char);
+            s.push(digits[(b & 0xf) as usize] as char);
+        }
+        s
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_uuid(bytes: [u8; 16]) -> String {
+        let hex = to_hex(bytes, b"0123456789abcdef");
+        let mut s = String::with_capacity(36);
+        for (i, part) in [
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..],
+        ]
+        .iter()
+        .enumerate()
+        {
+            if i > 0 {
+                s.push('-');
+            }
+            s.push_str(part);
+        }
+        s
+    }
+
+    /// Derives `count` 64-bit values from the 128-bit MurmurHash3 sum of data.
+    ///
+    /// The 128-bit hash is computed only once, then expanded with SplitMix64
+    /// seeded by `h1 ^ h2`. It's much cheaper than hashing the data `count`
+    /// times with different seeds. Note the expanded values are not
+    /// MurmurHash3 sums themselves, they are only derived from one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` is shorter than `count`.
+    pub fn expand_64(bytes: &[u8], seed: u32, count: usize, out: &mut [u64]) {
+        let (h1, h2) = murmurhash3_x64_128(bytes, seed);
+        let mut state = h1 ^ h2;
+        for o in &mut out[..count] {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            *o = z ^ (z >> 31);
+        }
+    }
+
+    /// Combines two 128-bit hashes into one.
+    ///
+    /// `b` is mixed into `a` as a block and the result is finalized the
+    /// same way as a MurmurHash3 sum, so the result is well distributed.
+    /// Order matters: `combine128(a, b)` is generally different from
+    /// `combine128(b, a)`, and the operation is not associative either.
+    /// Note the result is not the hash of the concatenated data.
+    pub fn combine128(a: (u64, u64), b: (u64, u64)) -> (u64, u64) {
+        let (mut h1, mut h2) = feed128(a.0, a.1, b.0, b.1);
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+        h1 = fmix64(h1);
+        h2 = fmix64(h2);
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+        (h1, h2)
+    }
+
+    /// Merges 128-bit hashes of parts into one fingerprint.
+    ///
+    /// Each part is fed as a block, and the result is the same as
+    /// `murmurhash3_x64_128` with seed 0 of the little endian bytes of all
+    /// parts concatenated, so it's stable. It's sensitive to the order of
+    /// parts. Note it's NOT the hash of the concatenated data of parts,
+    /// MurmurHash3 is sequential and can't be computed from hashes of
+    /// parts, so it's a different fingerprint of the data.
+    pub fn merge128(parts: &[(u64, u64)]) -> (u64, u64) {
+        let mut h1 = 0;
+        let mut h2 = 0;
+        for &(k1, k2) in parts {
+            let res = feed128(h1, h2, k1, k2);
+            h1 = res.0;
+            h2 = res.1;
+        }
+        finalize128(h1, h2, parts.len() as u64 * 16)
+    }
+
+    /// Hashes data in chunks of `chunk_size` bytes in parallel and merges
+    /// the hashes with `merge128`.
+    ///
+    /// The last chunk may be shorter. The result depends on `chunk_size`,
+    /// and it's NOT the same as `murmurhash3_x64_128(data, seed)`, see
+    /// `merge128`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    #[cfg(feature = "rayon")]
+    pub fn hash_chunks_parallel(data: &[u8], chunk_size: usize, seed: u32) -> (u64, u64) {
+        use rayon::prelude::*;
+
+        let parts: Vec<(u64, u64)> = data
+            .par_chunks(chunk_size)
+            .map(|c| murmurhash3_x64_128(c, seed))
+            .collect();
+        merge128(&parts)
+    }
+
+    /// How many bytes ahead of the current block to prefetch.
+    #[cfg(all(feature = "perf", target_arch = "x86_64", not(feature = "safe")))]
+    const PREFETCH_DISTANCE: usize = 512;
 
+    /// Hints the CPU to load the data a few cache lines ahead of `p`.
+    ///
+    /// It's a no-op unless `perf` feature is enabled on x86_64, and `safe`
+    /// feature is disabled. Prefetch never faults, so it's fine to point
+    /// past the end of data.
+    #[cfg(not(feature = "safe"))]
+    #[inline(always)]
+    fn prefetch(_p: *const u8) {
+        #[cfg(all(feature = "perf", target_arch = "x86_64"))]
+        // SAFETY: SSE is always available on x86_64, and prefetch doesn't
+        // dereference the pointer.
         unsafe {
-            finish_tail128(
-                start as *const u8,
-                bytes.len() % 16,
-                bytes.len() as u64,
-                h1,
-                h2,
-            )
+            use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+            _mm_prefetch::<_MM_HINT_T0>(_p.wrapping_add(PREFETCH_DISTANCE) as *const i8);
         }
     }
 
+    /// Reads a 16-byte block at `p` as two little endian words.
+    ///
+    /// # Safety
+    ///
+    /// `p` must be valid for reading 16 bytes, it doesn't need to be
+    /// aligned.
+    #[cfg(not(any(target_arch = "wasm32", feature = "portable", feature = "safe")))]
     #[inline]
-    fn fmix64(mut k: u64) -> u64 {
+    unsafe fn read_block(p: *const u8) -> (u64, u64) {
+        let k1 = ptr::read_unaligned(p as *const u64);
+        let k2 = ptr::read_unaligned(p.add(8) as *const u64);
+        (u64::from_le(k1), u64::from_le(k2))
+    }
+
+    /// Reads a 16-byte block at `p` as two little endian words.
+    ///
+    /// wasm32 and the `portable` feature take the portable path, which
+    /// doesn't depend on how the target lowers unaligned pointer reads.
+    ///
+    /// # Safety
+    ///
+    /// `p` must be valid for reading 16 bytes.
+    #[cfg(all(
+        any(target_arch = "wasm32", feature = "portable"),
+        not(feature = "safe")
+    ))]
+    #[inline]
+    unsafe fn read_block(p: *const u8) -> (u64, u64) {
+        let block = &*(p as *const [u8; 16]);
+        (
+            u64::from_le_bytes(block[..8].try_into().unwrap()),
+            u64::from_le_bytes(block[8..].try_into().unwrap()),
+        )
+    }
+
+    /// Mixes the bits of a word, it's the finalization mix of the 128-bit
+    /// hash.
+    #[inline]
+    pub fn fmix64(mut k: u64) -> u64 {
         k ^= k >> 33;
         k = k.wrapping_mul(0xff51afd7ed558ccd);
         k ^= k >> 33;
@@ -117,8 +969,10 @@ mod hash128 {
         k ^ (k >> 33)
     }
 
+    /// Feeds a block of two little endian words `k1` and `k2` to the
+    /// state `(h1, h2)` and returns the new state.
     #[inline]
-    fn feed128(mut h1: u64, mut h2: u64, mut k1: u64, mut k2: u64) -> (u64, u64) {
+    pub fn feed128(mut h1: u64, mut h2: u64, mut k1: u64, mut k2: u64) -> (u64, u64) {
         k1 = k1.wrapping_mul(C1);
         k1 = k1.rotate_left(31);
         k1 = k1.wrapping_mul(C2);
@@ -128,180 +982,596 @@ mod hash128 {
         h1 = h1.wrapping_add(h2);
         h1 = h1.wrapping_mul(5).wrapping_add(C3);
 
-        k2 = k2.wrapping_mul(C2);
-        k2 = k2.rotate_left(33);
-        k2 = k2.wrapping_mul(C1);
+        k2 = k2.wrapping_mul(C2);
+        k2 = k2.rotate_left(33);
+        k2 = k2.wrapping_mul(C1);
+
+        h2 ^= k2;
+        h2 = h2.rotate_left(31);
+        h2 = h2.wrapping_add(h1);
+        h2 = h2.wrapping_mul(5).wrapping_add(C4);
+
+        (h1, h2)
+    }
+
+    /// Reads less than 8 bytes as a little endian word, missing bytes
+    /// are zeros.
+    ///
+    /// It uses at most three loads instead of a branch per byte.
+    #[cfg(not(feature = "ct"))]
+    #[inline]
+    fn read_partial_u64(bytes: &[u8]) -> u64 {
+        let len = bytes.len();
+        if len >= 4 {
+            // The two loads overlap when len < 8, and the overlapped bytes
+            // are the same in both.
+            let lo = u32::from_le_bytes(bytes[..4].try_into().unwrap()) as u64;
+            let hi = u32::from_le_bytes(bytes[len - 4..].try_into().unwrap()) as u64;
+            lo | hi << (8 * (len - 4))
+        } else if len > 0 {
+            bytes[0] as u64
+                | (bytes[len / 2] as u64) << (8 * (len / 2))
+                | (bytes[len - 1] as u64) << (8 * (len - 1))
+        } else {
+            0
+        }
+    }
+
+    /// Reads a tail of less than 16 bytes as two little endian words,
+    /// missing bytes are zeros.
+    #[cfg(not(feature = "ct"))]
+    #[inline]
+    fn read_tail128(tail: &[u8]) -> (u64, u64) {
+        if tail.len() >= 8 {
+            (
+                u64::from_le_bytes(tail[..8].try_into().unwrap()),
+                read_partial_u64(&tail[8..]),
+            )
+        } else {
+            (read_partial_u64(tail), 0)
+        }
+    }
+
+    /// Reads a tail of less than 16 bytes as two little endian words,
+    /// missing bytes are zeros.
+    ///
+    /// With `ct` feature, all 16 positions are loaded and masked, so the
+    /// same operations are executed for all tail lengths.
+    #[cfg(feature = "ct")]
+    #[inline]
+    fn read_tail128(tail: &[u8]) -> (u64, u64) {
+        let mut block = [0; 16];
+        crate::read_tail_ct(tail, &mut block);
+        (
+            u64::from_le_bytes(block[..8].try_into().unwrap()),
+            u64::from_le_bytes(block[8..].try_into().unwrap()),
+        )
+    }
+
+    /// Mixes the tail of data into the state `(h1, h2)` and finalizes it,
+    /// `total` is the length of all data, including the tail.
+    ///
+    /// The tail must be shorter than a block, debug builds panic otherwise.
+    #[inline]
+    pub fn finish_tail128(tail: &[u8], total: u64, mut h1: u64, mut h2: u64) -> (u64, u64) {
+        debug_assert!(
+            tail.len() < 16,
+            "tail of {} bytes is not shorter than a block",
+            tail.len()
+        );
+        // Missing bytes are zeros, and mixing a zero word is a no-op, so
+        // all tail lengths share the same mixing path.
+        let (mut k1, mut k2) = read_tail128(tail);
+
+        k1 = k1.wrapping_mul(C1);
+        k1 = k1.rotate_left(31);
+        k1 = k1.wrapping_mul(C2);
+        h1 ^= k1;
+
+        k2 = k2.wrapping_mul(C2);
+        k2 = k2.rotate_left(33);
+        k2 = k2.wrapping_mul(C1);
+        h2 ^= k2;
+
+        finalize128(h1, h2, total)
+    }
+
+    /// Finalizes the state `(h1, h2)` after all data, including the tail,
+    /// is fed, `total` is the length of all data.
+    #[inline]
+    pub fn finalize128(mut h1: u64, mut h2: u64, total: u64) -> (u64, u64) {
+        h1 ^= total;
+        h2 ^= total;
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+        h1 = fmix64(h1);
+        h2 = fmix64(h2);
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+        (h1, h2)
+    }
+
+    /// Diagnostics of a write returned by `Hasher128::write_report`.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct WriteReport {
+        /// Count of blocks mixed by the write, including a block completed
+        /// with bytes buffered by previous writes.
+        pub blocks_processed: usize,
+        /// Count of bytes left in the buffer after the write.
+        pub tail_buffered: usize,
+    }
+
+    /// A 128-bit Murmur3 hasher.
+    ///
+    /// The total length of fed data is counted in `u64` and wraps around
+    /// on overflow. So the maximum supported length is `u64::MAX` bytes,
+    /// longer data doesn't panic but is hashed with its length modulo
+    /// 2^64, which can only happen with a crafted restored state in
+    /// practice.
+    #[derive(Clone)]
+    #[repr(C)]
+    pub struct Hasher128 {
+        pub(crate) h1: u64,
+        pub(crate) h2: u64,
+        pub(crate) buf: [u8; 16],
+        pub(crate) len: usize,
+        pub(crate) consume: u64,
+        /// The result cached by `peek128`, only valid if not `dirty`.
+        pub(crate) peeked: (u64, u64),
+        /// Whether bytes have been fed since `peeked` was cached.
+        pub(crate) dirty: bool,
+    }
+
+    impl Hasher128 {
+        /// The number of bytes fed to the hash state at a time.
+        pub const BLOCK_SIZE: usize = 16;
+
+        /// The number of bits of the hash result.
+        pub const DIGEST_BITS: u32 = 128;
+
+        /// Creates a hasher with given seed.
+        ///
+        /// It's a `const fn`, so preconfigured hashers can be embedded in
+        /// constants and static tables, and cloned when needed:
+        /// ```
+        /// use std::hash::Hasher;
+        ///
+        /// const HASHER: mur3::Hasher128 = mur3::Hasher128::with_seed(42);
+        ///
+        /// let mut hasher = HASHER.clone();
+        /// hasher.write(b"hello");
+        /// assert_eq!(hasher.finish128(), mur3::murmurhash3_x64_128(b"hello", 42));
+        /// ```
+        pub const fn with_seed(seed: u32) -> Hasher128 {
+            Hasher128 {
+                h1: seed as u64,
+                h2: seed as u64,
+                buf: [0; 16],
+                len: 0,
+                consume: 0,
+                peeked: (0, 0),
+                dirty: true,
+            }
+        }
+
+        /// Creates a hasher with a seed mixed by `fmix64`.
+        ///
+        /// The seed is only xored into the state by `with_seed`, so with
+        /// sequential seeds like `0, 1, 2`, short keys produce related
+        /// hashes, for example, an empty key with seed 1 and `[0]` with
+        /// seed 0 hash the same. Mixing the seed first spreads it to all
+        /// bits of both halves of the state, which suits generating many
+        /// hash functions from sequential seeds.
+        ///
+        /// It's an extension of mur3, the hashes are NOT compatible with
+        /// the canonical MurmurHash3 of any seed.
+        pub fn with_mixed_seed(seed: u32) -> Hasher128 {
+            let mut hasher = Hasher128::with_seed(0);
+            hasher.h1 = fmix64(seed as u64 ^ C1);
+            hasher.h2 = fmix64(seed as u64 ^ C2);
+            hasher
+        }
+
+        /// Resets the hasher to the initial state with given seed.
+        ///
+        /// The hasher behaves exactly like `Hasher128::with_seed(seed)`
+        /// afterwards, so it can be reused across keys that need
+        /// different seeds.
+        #[inline]
+        pub fn reset_with_seed(&mut self, seed: u32) {
+            *self = Hasher128::with_seed(seed);
+        }
+
+        /// Creates a hasher with a seed given in little endian bytes.
+        ///
+        /// It's the same as `with_seed(u32::from_le_bytes(seed))`.
+        #[inline]
+        pub fn with_seed_bytes(seed: [u8; 4]) -> Hasher128 {
+            Hasher128::with_seed(u32::from_le_bytes(seed))
+        }
+
+        /// Creates a hasher with a seed given in big endian bytes.
+        ///
+        /// It's the same as `with_seed(u32::from_be_bytes(seed))`.
+        #[inline]
+        pub fn with_seed_bytes_be(seed: [u8; 4]) -> Hasher128 {
+            Hasher128::with_seed(u32::from_be_bytes(seed))
+        }
+
+        /// Creates a hasher with a non-zero seed.
+        ///
+        /// It's the same as `with_seed(seed.get())`, for code that treats
+        /// seed 0 as unseeded.
+        #[inline]
+        pub fn with_nonzero_seed(seed: NonZeroU32) -> Hasher128 {
+            Hasher128::with_seed(seed.get())
+        }
+
+        #[inline]
+        fn feed(&mut self, k1: u64, k2: u64) {
+            let (h1, h2) = feed128(self.h1, self.h2, k1, k2);
+
+            self.h1 = h1;
+            self.h2 = h2;
+            self.consume = self.consume.wrapping_add(16);
+            self.dirty = true;
+        }
+
+        /// Feeds the full buffer and empties it.
+        #[inline]
+        fn feed_buf(&mut self) {
+            let n1 = u64::from_le_bytes(self.buf[..8].try_into().unwrap());
+            let n2 = u64::from_le_bytes(self.buf[8..].try_into().unwrap());
+            self.len = 0;
+            self.feed(n1, n2);
+        }
+
+        /// Feeds a block of two little endian lanes to the hasher.
+        ///
+        /// It's the same as feeding `k1.to_le_bytes()` followed by
+        /// `k2.to_le_bytes()`, but skips the byte buffering. It must only
+        /// be called when the hasher is block aligned, that is, the
+        /// number of bytes fed so far is a multiple of 16. Otherwise the
+        /// result is unspecified and debug builds will panic.
+        #[inline]
+        pub fn feed_block(&mut self, k1: u64, k2: u64) {
+            debug_assert_eq!(self.len, 0, "hasher is not block aligned");
+            self.feed(k1, k2);
+        }
+
+        /// Feeds two `u64` words as a block to the hasher.
+        ///
+        /// Words are interpreted in little endian, so it's the same as
+        /// feeding `k1.to_le_bytes()` followed by `k2.to_le_bytes()` on
+        /// all platforms. It's an alias of `feed_block` for callers that
+        /// parse data into words, the same restriction applies: nothing
+        /// may be buffered, that is, the number of bytes fed so far must
+        /// be a multiple of 16, otherwise debug builds will panic.
+        #[inline]
+        pub fn write_u64_pair(&mut self, k1: u64, k2: u64) {
+            self.feed_block(k1, k2);
+        }
+
+        /// Feeds a string to the hasher.
+        ///
+        /// It's exactly the same as feeding `s.as_bytes()`, neither
+        /// terminator nor length is fed.
+        #[inline]
+        pub fn write_str(&mut self, s: &str) {
+            self.write(s.as_bytes());
+        }
+
+        /// Feeds all chunks to the hasher in order.
+        ///
+        /// It's the same as feeding the chunks one by one.
+        #[inline]
+        pub fn write_iter<'a, I: IntoIterator<Item = &'a [u8]>>(&mut self, chunks: I) {
+            for chunk in chunks {
+                self.write(chunk);
+            }
+        }
+
+        /// Feeds the length of data followed by data to the hasher.
+        ///
+        /// The length is fed as a `u64` in little endian, 8 bytes on all
+        /// platforms. Prefixing lengths makes sequences of variable length
+        /// fields unambiguous, for example, `["ab", "c"]` and `["a", "bc"]`
+        /// hash differently, while plain `write` concatenates them.
+        #[inline]
+        pub fn write_length_prefixed(&mut self, data: &[u8]) {
+            self.write(&(data.len() as u64).to_le_bytes());
+            self.write(data);
+        }
+
+        /// Feeds data to the hasher and reports how it's processed.
+        ///
+        /// The hash is the same as feeding data with `write`, the report
+        /// tells how many blocks are mixed and how many bytes are left
+        /// buffered, for tuning chunk sizes.
+        pub fn write_report(&mut self, bytes: &[u8]) -> WriteReport {
+            let consume = self.consume;
+            self.write(bytes);
+            WriteReport {
+                blocks_processed: (self.consume.wrapping_sub(consume) / 16) as usize,
+                tail_buffered: self.len,
+            }
+        }
+
+        /// Feeds a tag byte followed by the payload to the hasher.
+        ///
+        /// It's the same as feeding `[tag]` and then `bytes`, which makes
+        /// the same payload under different tags hash differently, for
+        /// example, variants of an enum. Note the tag doesn't delimit the
+        /// payload, so when several tagged payloads of variable length are
+        /// fed, their lengths should be fed as well to avoid ambiguity.
+        #[inline]
+        pub fn write_tagged(&mut self, tag: u8, bytes: &[u8]) {
+            self.write(&[tag]);
+            self.write(bytes);
+        }
+
+        /// Feeds data to the hasher and reports the state after every
+        /// block.
+        ///
+        /// `trace` is called with `(h1, h2)` each time a block is mixed,
+        /// including a block completed with bytes buffered by previous
+        /// writes. The hash is the same as feeding data with `write`. It's
+        /// for pinpointing where two implementations diverge.
+        #[cfg(feature = "trace")]
+        pub fn trace_write(&mut self, mut bytes: &[u8], trace: &mut dyn FnMut(u64, u64)) {
+            while !bytes.is_empty() {
+                let cnt = (16 - self.len).min(bytes.len());
+                self.write(&bytes[..cnt]);
+                bytes = &bytes[cnt..];
+                if self.len == 0 {
+                    trace(self.h1, self.h2);
+                }
+            }
+        }
+
+        /// Gets the number of bytes fed to the hasher so far.
+        #[inline]
+        pub fn bytes_written(&self) -> u64 {
+            self.consume.wrapping_add(self.len as u64)
+        }
+
+        /// Gets the number of bytes needed to complete the current block.
+        ///
+        /// It's 16 if nothing is buffered, so writes sliced on block
+        /// boundaries by it are mixed without buffering.
+        #[inline]
+        pub fn block_remaining(&self) -> usize {
+            16 - self.len
+        }
+
+        /// Gets the 128-bit hash result.
+        ///
+        /// This function doesn't have any side effect. So calling it
+        /// multiple times without feeding more data will return the
+        /// same result. New data will resume calculation from last state.
+        ///
+        /// If the result has been cached by `peek128` and no bytes have
+        /// been fed since then, the cached result is returned.
+        #[inline]
+        pub fn finish128(&self) -> (u64, u64) {
+            if !self.dirty {
+                return self.peeked;
+            }
+            finish_tail128(
+                &self.buf[..self.len],
+                self.consume.wrapping_add(self.len as u64),
+                self.h1,
+                self.h2,
+            )
+        }
+
+        /// Gets the 128-bit hash result with `len` as the total length.
+        ///
+        /// The length mixed into the finalization is `len` instead of the
+        /// number of bytes fed, which is a compatibility escape hatch for
+        /// nonstandard ports that fold a declared length into the hash.
+        /// `finish128_with_len(self.bytes_written())` is the same as
+        /// `finish128`, other lengths produce hashes that are NOT
+        /// MurmurHash3 sums of the fed data.
+        #[inline]
+        pub fn finish128_with_len(&self, len: u64) -> (u64, u64) {
+            finish_tail128(&self.buf[..self.len], len, self.h1, self.h2)
+        }
+
+        /// Gets the 128-bit hash result and caches it.
+        ///
+        /// The result is the same as `finish128`. Finalization mixes the
+        /// buffered bytes and the length every time, which costs about as
+        /// much as hashing a block or two and is wasted when checkpointing
+        /// in a loop without feeding new data. `peek128` caches the result,
+        /// so repeated calls of `peek128` and `finish128` are cheap until
+        /// more bytes are fed.
+        #[inline]
+        pub fn peek128(&mut self) -> (u64, u64) {
+            let h = self.finish128();
+            self.peeked = h;
+            self.dirty = false;
+            h
+        }
+
+        /// Forks the hasher into one continuation per branch id.
+        ///
+        /// Each returned hasher is a clone of current hasher fed with the
+        /// little endian bytes of its branch id, so children that are fed
+        /// the same data later still produce different hashes. It's useful
+        /// for hashing tree structures where position matters.
+        #[cfg(feature = "alloc")]
+        pub fn fork(&self, branch_ids: &[u32]) -> Vec<Hasher128> {
+            branch_ids
+                .iter()
+                .map(|id| {
+                    let mut h = self.clone();
+                    h.write(&id.to_le_bytes());
+                    h
+                })
+                .collect()
+        }
+
+        /// Gets the first 64 bits of the 128-bit hash result.
+        ///
+        /// It's the same as `self.finish128().0` and `Hasher::finish`, and
+        /// not cheaper than `finish128`.
+        #[inline]
+        pub fn finish64(&self) -> u64 {
+            self.finish128().0
+        }
+
+        /// Gets the low 32 bits of the 128-bit hash result.
+        ///
+        /// It's `self.finish128().0 as u32`, which is a truncation of the
+        /// 128-bit hash, not the result of `murmurhash3_x86_32` or
+        /// `Hasher32`, a different algorithm.
+        #[inline]
+        pub fn finish_low32(&self) -> u32 {
+            self.finish128().0 as u32
+        }
+
+        /// Gets the 128-bit hash result as bytes.
+        ///
+        /// The bytes are `h1` followed by `h2`, both in little endian, which
+        /// is the same as the output of the reference implementation on
+        /// little endian machines.
+        #[inline]
+        pub fn finish_bytes(&self) -> [u8; 16] {
+            to_bytes(self.finish128())
+        }
 
-        h2 ^= k2;
-        h2 = h2.rotate_left(31);
-        h2 = h2.wrapping_add(h1);
-        h2 = h2.wrapping_mul(5).wrapping_add(C4);
+        /// Gets the 128-bit hash result as big endian bytes.
+        ///
+        /// The bytes are `h1` followed by `h2`, both in big endian, for
+        /// storage formats that want big endian. Only the serialization
+        /// differs from `finish_bytes`, the hash is the same.
+        #[inline]
+        pub fn finish_bytes_be(&self) -> [u8; 16] {
+            to_bytes_be(self.finish128())
+        }
 
-        (h1, h2)
-    }
+        /// Gets the 128-bit hash result as a lowercase hex string.
+        ///
+        /// The string is the hex of the bytes returned by `finish_bytes`.
+        #[cfg(feature = "alloc")]
+        pub fn finish_hex(&self) -> String {
+            to_hex(self.finish_bytes(), b"0123456789abcdef")
+        }
 
-    #[inline]
-    unsafe fn read_u64(start: *const u8, len: usize) -> (*const u8, usize, u64) {
-        if len >= 8 {
-            return (
-                start.add(8),
-                len - 8,
-                u64::from_le((start as *const u64).read_unaligned()),
-            );
+        /// Gets the 128-bit hash result as an uppercase hex string.
+        ///
+        /// The string is the hex of the bytes returned by `finish_bytes`.
+        #[cfg(feature = "alloc")]
+        pub fn finish_hex_upper(&self) -> String {
+            to_hex(self.finish_bytes(), b"0123456789ABCDEF")
         }
-        let res = read_partial_u64(start, len);
-        (start, 0, res)
     }
 
-    #[inline]
-    unsafe fn read_partial_u64(start: *const u8, len: usize) -> u64 {
-        let mut res = 0;
-        match len {
-            7 => super::read_7(&mut res, start),
-            6 => super::read_6(&mut res, start),
-            5 => super::read_5(&mut res, start),
-            4 => super::read_4(&mut res, start),
-            3 => super::read_3(&mut res, start),
-            2 => super::read_2(&mut res, start),
-            1 => super::read_1(&mut res, start),
-            _ => (),
-        }
-        res
+    /// A `Hasher` that can produce a 128-bit result.
+    ///
+    /// `Hasher::finish` only returns 64 bits, this trait keeps the full
+    /// result reachable from generic code and trait objects.
+    pub trait Hasher128Ext: Hasher {
+        /// Gets the 128-bit hash result.
+        fn finish128(&self) -> (u64, u64);
     }
 
-    #[inline]
-    unsafe fn finish_tail128(
-        tail: *const u8,
-        remain: usize,
-        total: u64,
-        mut h1: u64,
-        mut h2: u64,
-    ) -> (u64, u64) {
-        if remain > 0 {
-            let res = read_u64(tail, remain);
-            let mut k = res.2;
-            k = k.wrapping_mul(C1);
-            k = k.rotate_left(31);
-            k = k.wrapping_mul(C2);
-            h1 ^= k;
-
-            if res.1 > 0 {
-                k = read_partial_u64(res.0, res.1);
-                k = k.wrapping_mul(C2);
-                k = k.rotate_left(33);
-                k = k.wrapping_mul(C1);
-                h2 ^= k;
-            }
+    impl Hasher128Ext for Hasher128 {
+        #[inline]
+        fn finish128(&self) -> (u64, u64) {
+            Hasher128::finish128(self)
         }
-
-        h1 ^= total;
-        h2 ^= total;
-        h1 = h1.wrapping_add(h2);
-        h2 = h2.wrapping_add(h1);
-        h1 = fmix64(h1);
-        h2 = fmix64(h2);
-        h1 = h1.wrapping_add(h2);
-        h2 = h2.wrapping_add(h1);
-        (h1, h2)
     }
 
-    /// A 128-bit Murmur3 hasher.
-    #[repr(C)]
-    pub struct Hasher128 {
-        h1: u64,
-        h2: u64,
-        buf: [u8; 16],
-        len: usize,
-        consume: u64,
+    /// Compares the internal states of two hashers.
+    ///
+    /// Bytes are always mixed in whole blocks and the rest are buffered,
+    /// so two hashers with the same seed that are fed the same bytes are
+    /// equal no matter how the bytes are split into writes. Stale bytes
+    /// in the buffer beyond the buffered length are ignored. Note that
+    /// it compares raw states, hashers in different states may still
+    /// produce the same hash by collision.
+    impl PartialEq for Hasher128 {
+        fn eq(&self, other: &Hasher128) -> bool {
+            self.h1 == other.h1
+                && self.h2 == other.h2
+                && self.len == other.len
+                && self.consume == other.consume
+                && self.buf[..self.len] == other.buf[..other.len]
+        }
     }
 
-    impl Hasher128 {
-        /// Creates a hasher with given seed.
-        pub fn with_seed(seed: u32) -> Hasher128 {
-            Hasher128 {
-                h1: seed as u64,
-                h2: seed as u64,
-                buf: [0; 16],
-                len: 0,
-                consume: 0,
-            }
+    impl Eq for Hasher128 {}
+
+    // Only the buffered bytes are shown, stale bytes of the buffer don't
+    // affect the sum and are ignored by `PartialEq` as well.
+    impl fmt::Debug for Hasher128 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Hasher128")
+                .field("h1", &self.h1)
+                .field("h2", &self.h2)
+                .field("buf", &&self.buf[..self.len])
+                .field("consume", &self.consume)
+                .finish()
         }
+    }
 
+    impl fmt::Write for Hasher128 {
+        /// Feeds the bytes of a string to the hasher.
+        ///
+        /// It makes `write!` feed formatted output without allocation.
         #[inline]
-        fn feed(&mut self, k1: u64, k2: u64) {
-            let (h1, h2) = feed128(self.h1, self.h2, k1, k2);
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.write(s.as_bytes());
+            Ok(())
+        }
+    }
 
-            self.h1 = h1;
-            self.h2 = h2;
-            self.consume += 16;
+    impl<'a> Extend<&'a [u8]> for Hasher128 {
+        /// Feeds all chunks to the hasher in order.
+        #[inline]
+        fn extend<I: IntoIterator<Item = &'a [u8]>>(&mut self, chunks: I) {
+            self.write_iter(chunks);
         }
+    }
 
-        /// Gets the 128-bit hash result.
+    impl Hasher for Hasher128 {
+        /// Feeds a single byte to the hasher.
         ///
-        /// This function doesn't have any side effect. So calling it
-        /// multiple times without feeding more data will return the
-        /// same result. New data will resume calculation from last state.
+        /// It's the same as `write(&[i])` but appends to the buffer
+        /// directly, which is faster for feeding byte by byte.
         #[inline]
-        pub fn finish128(&self) -> (u64, u64) {
-            unsafe {
-                finish_tail128(
-                    self.buf.as_ptr(),
-                    self.len,
-                    self.consume + self.len as u64,
-                    self.h1,
-                    self.h2,
-                )
+        fn write_u8(&mut self, i: u8) {
+            self.buf[self.len] = i;
+            self.len += 1;
+            self.dirty = true;
+            if self.len == 16 {
+                self.feed_buf();
             }
         }
-    }
 
-    impl Hasher for Hasher128 {
         /// Feeds a byte slice to the hasher.
         fn write(&mut self, mut bytes: &[u8]) {
+            self.dirty = true;
             if self.len + bytes.len() < 16 {
-                unsafe {
-                    ptr::copy_nonoverlapping(
-                        bytes.as_ptr(),
-                        self.buf.as_mut_ptr().add(self.len),
-                        bytes.len(),
-                    );
-                }
+                self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
                 self.len += bytes.len();
                 return;
             } else if self.len != 0 {
-                let (n1, n2) = unsafe {
-                    let cnt = 16 - self.len;
-                    ptr::copy_nonoverlapping(
-                        bytes.as_ptr(),
-                        self.buf.as_mut_ptr().add(self.len),
-                        cnt,
-                    );
-                    bytes = slice::from_raw_parts(bytes.as_ptr().add(cnt), bytes.len() - cnt);
-                    let n1 = ptr::read(self.buf.as_ptr() as *const u64);
-                    let n2 = ptr::read(self.buf.as_ptr().add(8) as *const u64);
-                    self.len = 0;
-                    (u64::from_le(n1), u64::from_le(n2))
-                };
-                self.feed(n1, n2);
-            }
-            let mut start = bytes.as_ptr();
-            for _ in 0..bytes.len() / 16 {
-                let (n1, n2) = unsafe {
-                    let n1 = ptr::read_unaligned(start as *const u64);
-                    start = start.add(8);
-                    let n2 = ptr::read_unaligned(start as *const u64);
-                    start = start.add(8);
-                    (u64::from_le(n1), u64::from_le(n2))
-                };
-                self.feed(n1, n2);
-            }
-            unsafe {
-                let len = bytes.len() % 16;
-                if len > 0 {
-                    ptr::copy_nonoverlapping(start, self.buf.as_mut_ptr(), len);
-                }
-                self.len = len;
+                let cnt = 16 - self.len;
+                self.buf[self.len..].copy_from_slice(&bytes[..cnt]);
+                bytes = &bytes[cnt..];
+                self.feed_buf();
             }
+            let (h1, h2) = backend::detect().hash128_blocks(bytes, self.h1, self.h2);
+            let nblocks = bytes.len() / 16;
+            self.h1 = h1;
+            self.h2 = h2;
+            self.consume = self.consume.wrapping_add(nblocks as u64 * 16);
+            let tail = &bytes[nblocks * 16..];
+            self.buf[..tail.len()].copy_from_slice(tail);
+            self.len = tail.len();
         }
 
         /// Gets the 64-bit hash value.
@@ -315,8 +1585,10 @@ mod hash128 {
 }
 
 mod hash32 {
+    use core::fmt;
     use core::hash::Hasher;
-    use core::{ptr, slice};
+    #[cfg(not(any(target_arch = "wasm32", feature = "portable", feature = "safe")))]
+    use core::ptr;
 
     const C1: u32 = 0xcc9e2d51;
     const C2: u32 = 0x1b873593;
@@ -324,8 +1596,39 @@ mod hash32 {
     const C4: u32 = 0x85ebca6b;
     const C5: u32 = 0xc2b2ae35;
 
+    /// Reads a 4-byte block at `p` as a little endian word.
+    ///
+    /// # Safety
+    ///
+    /// `p` must be valid for reading 4 bytes, it doesn't need to be
+    /// aligned.
+    #[cfg(not(any(target_arch = "wasm32", feature = "portable", feature = "safe")))]
+    #[inline]
+    unsafe fn read_block(p: *const u8) -> u32 {
+        u32::from_le(ptr::read_unaligned(p as *const u32))
+    }
+
+    /// Reads a 4-byte block at `p` as a little endian word.
+    ///
+    /// wasm32 and the `portable` feature take the portable path, which
+    /// doesn't depend on how the target lowers unaligned pointer reads.
+    ///
+    /// # Safety
+    ///
+    /// `p` must be valid for reading 4 bytes.
+    #[cfg(all(
+        any(target_arch = "wasm32", feature = "portable"),
+        not(feature = "safe")
+    ))]
+    #[inline]
+    unsafe fn read_block(p: *const u8) -> u32 {
+        u32::from_le_bytes(*(p as *const [u8; 4]))
+    }
+
+    /// Mixes the bits of a word, it's the finalization mix of the 32-bit
+    /// hash.
     #[inline]
-    fn fmix32(mut h: u32) -> u32 {
+    pub fn fmix32(mut h: u32) -> u32 {
         h ^= h >> 16;
         h = h.wrapping_mul(C4);
         h ^= h >> 13;
@@ -333,8 +1636,10 @@ mod hash32 {
         h ^ (h >> 16)
     }
 
+    /// Feeds a little endian word `k` to the state `h` and returns the new
+    /// state.
     #[inline]
-    fn feed32(mut h: u32, mut k: u32) -> u32 {
+    pub fn feed32(mut h: u32, mut k: u32) -> u32 {
         k = k.wrapping_mul(C1);
         k = k.rotate_left(15);
         k = k.wrapping_mul(C2);
@@ -344,62 +1649,183 @@ mod hash32 {
         h.wrapping_mul(5).wrapping_add(C3)
     }
 
+    /// Mixes the tail of data into the state `h` and finalizes it, `total`
+    /// is the length of all data, including the tail.
+    ///
+    /// The tail must be shorter than a block, debug builds panic otherwise.
     #[inline]
-    unsafe fn finish_tail32(mut tail: *const u8, end: *const u8, total: u64, mut h: u32) -> u32 {
-        if tail != end {
-            let mut k: u32 = 0;
-            for i in 0..3 {
-                k ^= ((*tail) as u32) << (8 * i);
-                tail = tail.add(1);
-                if tail == end {
-                    break;
-                }
-            }
-            k = k.wrapping_mul(C1);
-            k = k.rotate_left(15);
-            k = k.wrapping_mul(C2);
-            h ^= k;
+    pub fn finish_tail32(tail: &[u8], total: u64, mut h: u32) -> u32 {
+        debug_assert!(
+            tail.len() < 4,
+            "tail of {} bytes is not shorter than a block",
+            tail.len()
+        );
+        // Missing bytes are zeros, and mixing a zero word is a no-op, so
+        // all tail lengths share the same path without branches.
+        let mut block = [0; 4];
+        #[cfg(not(feature = "ct"))]
+        for (i, b) in block.iter_mut().enumerate() {
+            *b = tail.get(i).copied().unwrap_or(0);
         }
+        #[cfg(feature = "ct")]
+        crate::read_tail_ct(tail, &mut block);
+        let mut k = u32::from_le_bytes(block);
+        k = k.wrapping_mul(C1);
+        k = k.rotate_left(15);
+        k = k.wrapping_mul(C2);
+        h ^= k;
         h ^= total as u32;
         fmix32(h)
     }
 
+    /// Folds the high 32 bits of a length into the low 32 bits, so the
+    /// whole length is mixed by `finish_tail32`.
+    #[inline]
+    fn fold_len(total: u64) -> u64 {
+        total ^ (total >> 32)
+    }
+
     /// Gets the 32-bit MurmurHash3 sum of data.
     ///
     /// To feed multiple byte slices, use `Hasher32` instead.
+    ///
+    /// Only the low 32 bits of the length are mixed, so data of 4 GiB or
+    /// more is hashed as if its length were `len % 2^32`. The reference
+    /// implementation takes the length as `int`, which can't represent
+    /// such lengths at all, so there is no canonical result for them. Use
+    /// `murmurhash3_x86_32_long` to mix the whole length.
     pub fn murmurhash3_x86_32(bytes: &[u8], seed: u32) -> u32 {
+        // Small keys, including empty ones, are all tail.
+        if bytes.len() < 4 {
+            return finish_tail32(bytes, bytes.len() as u64, seed);
+        }
+        let h = feed_blocks(bytes, seed);
         let nblocks = bytes.len() / 4;
-        let mut h = seed;
-        let mut start = bytes.as_ptr();
+        finish_tail32(&bytes[nblocks * 4..], bytes.len() as u64, h)
+    }
 
-        for _ in 0..nblocks {
-            let k = u32::from_le(unsafe { ptr::read_unaligned(start as *const u32) });
+    /// Feeds all complete blocks of `bytes` to the state, the tail is
+    /// left to the caller.
+    ///
+    /// It's the block loop shared by `murmurhash3_x86_32` and `Hasher32`.
+    #[cfg(not(feature = "safe"))]
+    #[cfg_attr(not(feature = "codegen-inline"), inline)]
+    #[cfg_attr(feature = "codegen-inline", inline(always))]
+    fn feed_blocks(bytes: &[u8], mut h: u32) -> u32 {
+        let mut start = bytes.as_ptr();
+        for _ in 0..bytes.len() / 4 {
+            // SAFETY: there are `bytes.len() / 4` blocks, each iteration
+            // reads one block at `start` and advances it by one block, so
+            // both reading and advancing stay within `bytes`.
+            let k = unsafe { read_block(start) };
             h = feed32(h, k);
             start = unsafe { start.add(4) };
         }
+        h
+    }
 
-        unsafe {
-            finish_tail32(
-                start as *const u8,
-                bytes.as_ptr().add(bytes.len()),
-                bytes.len() as u64,
+    /// Feeds all complete blocks of `bytes` to the state, the tail is
+    /// left to the caller.
+    ///
+    /// With `safe` feature, blocks are read by indexing slices.
+    #[cfg(feature = "safe")]
+    #[cfg_attr(not(feature = "codegen-inline"), inline)]
+    #[cfg_attr(feature = "codegen-inline", inline(always))]
+    fn feed_blocks(bytes: &[u8], mut h: u32) -> u32 {
+        for block in bytes.chunks_exact(4) {
+            h = feed32(
                 h,
-            )
+                u32::from_le_bytes([block[0], block[1], block[2], block[3]]),
+            );
         }
+        h
+    }
+
+    /// Gets the 32-bit MurmurHash3 sum of data, mixing the whole 64-bit
+    /// length.
+    ///
+    /// The high 32 bits of the length are xored into the low 32 bits
+    /// before mixing, so the result is the same as `murmurhash3_x86_32`
+    /// for data shorter than 4 GiB, and differs for larger data whose
+    /// lengths are equal modulo 2^32.
+    pub fn murmurhash3_x86_32_long(bytes: &[u8], seed: u32) -> u32 {
+        let h = feed_blocks(bytes, seed);
+        let nblocks = bytes.len() / 4;
+        finish_tail32(&bytes[nblocks * 4..], fold_len(bytes.len() as u64), h)
+    }
+
+    /// Gets the 32-bit MurmurHash3 sum of hierarchical parts by chaining.
+    ///
+    /// Every part is hashed with the hash of the previous part as seed,
+    /// and the first part with `seed`, which is a common pattern for
+    /// hierarchical keys like a namespace and a key in it. For example,
+    /// `murmurhash3_x86_32_chained(&[ns, key], 0)` equals
+    /// `murmurhash3_x86_32(key, murmurhash3_x86_32(ns, 0))`. It returns
+    /// `seed` if there is no part.
+    ///
+    /// Unlike hashing the concatenation, the boundaries of parts affect
+    /// the result, so `["ab", "c"]` and `["a", "bc"]` hash differently.
+    pub fn murmurhash3_x86_32_chained(parts: &[&[u8]], seed: u32) -> u32 {
+        parts
+            .iter()
+            .fold(seed, |seed, part| murmurhash3_x86_32(part, seed))
+    }
+
+    /// Combines a 32-bit value into a running 32-bit hash, like
+    /// `boost::hash_combine`.
+    ///
+    /// `value` is mixed into `seed` as a block and finalized, so the
+    /// result is the same as `murmurhash3_x86_32(&value.to_le_bytes(),
+    /// seed)` and is well distributed. It's a combiner of mur3, the
+    /// results differ from boost's, and composing hashes this way is not
+    /// the hash of the concatenated data. Order matters:
+    ///
+    /// ```
+    /// let h = [1, 2, 3].iter().fold(0, |h, v| mur3::hash_combine32(h, *v));
+    /// let r = [3, 2, 1].iter().fold(0, |h, v| mur3::hash_combine32(h, *v));
+    /// assert_ne!(h, r);
+    /// ```
+    #[inline]
+    pub fn hash_combine32(seed: u32, value: u32) -> u32 {
+        fmix32(feed32(seed, value) ^ 4)
+    }
+
+    /// Gets the 32-bit MurmurHash3 sum of anything that can be viewed as
+    /// bytes, like `String`, `Vec<u8>` and byte arrays.
+    ///
+    /// It's the same as `murmurhash3_x86_32(b.as_ref(), seed)`.
+    #[inline]
+    pub fn murmurhash3_x86_32_of<B: AsRef<[u8]>>(b: B, seed: u32) -> u32 {
+        murmurhash3_x86_32(b.as_ref(), seed)
     }
 
     /// A 32-bit Murmur3 hasher.
+    ///
+    /// The total length of fed data is counted in `u64` and wraps around
+    /// on overflow. So the maximum supported length is `u64::MAX` bytes,
+    /// longer data doesn't panic but is hashed with its length modulo
+    /// 2^64, which can only happen with a crafted restored state in
+    /// practice.
+    #[derive(Clone)]
     #[repr(C)]
     pub struct Hasher32 {
-        h: u32,
-        buf: [u8; 4],
-        len: usize,
-        consume: u64,
+        pub(crate) h: u32,
+        pub(crate) buf: [u8; 4],
+        pub(crate) len: usize,
+        pub(crate) consume: u64,
     }
 
     impl Hasher32 {
+        /// The number of bytes fed to the hash state at a time.
+        pub const BLOCK_SIZE: usize = 4;
+
+        /// The number of bits of the hash result.
+        pub const DIGEST_BITS: u32 = 32;
+
         /// Creates a hasher with given seed.
-        pub fn with_seed(seed: u32) -> Hasher32 {
+        ///
+        /// It's a `const fn`, like `Hasher128::with_seed`.
+        pub const fn with_seed(seed: u32) -> Hasher32 {
             Hasher32 {
                 h: seed,
                 buf: [0; 4],
@@ -411,7 +1837,37 @@ mod hash32 {
         #[inline]
         fn feed(&mut self, k: u32) {
             self.h = feed32(self.h, k);
-            self.consume += 4;
+            self.consume = self.consume.wrapping_add(4);
+        }
+
+        /// Feeds the full buffer and empties it.
+        #[inline]
+        fn feed_buf(&mut self) {
+            self.len = 0;
+            self.feed(u32::from_le_bytes(self.buf));
+        }
+
+        /// Feeds a string to the hasher.
+        ///
+        /// It's exactly the same as feeding `s.as_bytes()`, neither
+        /// terminator nor length is fed.
+        #[inline]
+        pub fn write_str(&mut self, s: &str) {
+            self.write(s.as_bytes());
+        }
+
+        /// Gets the number of bytes fed to the hasher so far.
+        #[inline]
+        pub fn bytes_written(&self) -> u64 {
+            self.consume.wrapping_add(self.len as u64)
+        }
+
+        /// Gets the number of bytes needed to complete the current block.
+        ///
+        /// It's 4 if nothing is buffered.
+        #[inline]
+        pub fn block_remaining(&self) -> usize {
+            4 - self.len
         }
 
         /// Gets the 32-bit hash result.
@@ -421,61 +1877,101 @@ mod hash32 {
         /// same result. New data will resume calculation from last state.
         #[inline]
         pub fn finish32(&self) -> u32 {
-            unsafe {
-                finish_tail32(
-                    self.buf.as_ptr(),
-                    self.buf.as_ptr().add(self.len),
-                    self.consume + self.len as u64,
-                    self.h,
-                )
-            }
+            finish_tail32(
+                &self.buf[..self.len],
+                self.consume.wrapping_add(self.len as u64),
+                self.h,
+            )
+        }
+
+        /// Gets the 32-bit hash result, mixing the whole 64-bit length.
+        ///
+        /// It's the same as `finish32` if less than 4 GiB are fed, see
+        /// `murmurhash3_x86_32_long`.
+        #[inline]
+        pub fn finish32_long(&self) -> u32 {
+            finish_tail32(
+                &self.buf[..self.len],
+                fold_len(self.bytes_written()),
+                self.h,
+            )
+        }
+    }
+
+    /// Compares the internal states of two hashers.
+    ///
+    /// Bytes are always mixed in whole blocks and the rest are buffered,
+    /// so two hashers with the same seed that are fed the same bytes are
+    /// equal no matter how the bytes are split into writes. Stale bytes
+    /// in the buffer beyond the buffered length are ignored. Note that
+    /// it compares raw states, hashers in different states may still
+    /// produce the same hash by collision.
+    impl PartialEq for Hasher32 {
+        fn eq(&self, other: &Hasher32) -> bool {
+            self.h == other.h
+                && self.len == other.len
+                && self.consume == other.consume
+                && self.buf[..self.len] == other.buf[..other.len]
+        }
+    }
+
+    impl Eq for Hasher32 {}
+
+    // Only the buffered bytes are shown, stale bytes of the buffer don't
+    // affect the sum and are ignored by `PartialEq` as well.
+    impl fmt::Debug for Hasher32 {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Hasher32")
+                .field("h", &self.h)
+                .field("buf", &&self.buf[..self.len])
+                .field("consume", &self.consume)
+                .finish()
+        }
+    }
+
+    impl fmt::Write for Hasher32 {
+        /// Feeds the bytes of a string to the hasher.
+        ///
+        /// It makes `write!` feed formatted output without allocation.
+        #[inline]
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.write(s.as_bytes());
+            Ok(())
         }
     }
 
     impl Hasher for Hasher32 {
+        /// Feeds a single byte to the hasher.
+        ///
+        /// It's the same as `write(&[i])` but appends to the buffer
+        /// directly, which is faster for feeding byte by byte.
+        #[inline]
+        fn write_u8(&mut self, i: u8) {
+            self.buf[self.len] = i;
+            self.len += 1;
+            if self.len == 4 {
+                self.feed_buf();
+            }
+        }
+
         /// Feeds a byte slice to the hasher.
         fn write(&mut self, mut bytes: &[u8]) {
             if self.len + bytes.len() < 4 {
-                unsafe {
-                    ptr::copy_nonoverlapping(
-                        bytes.as_ptr(),
-                        self.buf.as_mut_ptr().add(self.len),
-                        bytes.len(),
-                    );
-                }
+                self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
                 self.len += bytes.len();
                 return;
             } else if self.len != 0 {
-                let n = unsafe {
-                    let cnt = 4 - self.len;
-                    ptr::copy_nonoverlapping(
-                        bytes.as_ptr(),
-                        self.buf.as_mut_ptr().add(self.len),
-                        cnt,
-                    );
-                    bytes = slice::from_raw_parts(bytes.as_ptr().add(cnt), bytes.len() - cnt);
-                    let n = ptr::read(self.buf.as_ptr() as *const u32);
-                    self.len = 0;
-                    u32::from_le(n)
-                };
-                self.feed(n);
-            }
-            let mut start = bytes.as_ptr();
-            for _ in 0..bytes.len() / 4 {
-                let n = unsafe {
-                    let n = ptr::read_unaligned(start as *const u32);
-                    start = start.add(4);
-                    u32::from_le(n)
-                };
-                self.feed(n);
-            }
-            unsafe {
-                let len = bytes.len() % 4;
-                if len > 0 {
-                    ptr::copy_nonoverlapping(start, self.buf.as_mut_ptr(), len);
-                }
-                self.len = len;
+                let cnt = 4 - self.len;
+                self.buf[self.len..].copy_from_slice(&bytes[..cnt]);
+                bytes = &bytes[cnt..];
+                self.feed_buf();
             }
+            let nblocks = bytes.len() / 4;
+            self.h = feed_blocks(bytes, self.h);
+            self.consume = self.consume.wrapping_add(nblocks as u64 * 4);
+            let tail = &bytes[nblocks * 4..];
+            self.buf[..tail.len()].copy_from_slice(tail);
+            self.len = tail.len();
         }
 
         /// Gets the 64-bit hash value.
@@ -488,5 +1984,331 @@ mod hash32 {
     }
 }
 
-pub use hash128::{murmurhash3_x64_128, Hasher128};
-pub use hash32::{murmurhash3_x86_32, Hasher32};
+/// Copies `tail` to the front of `block` and zeros the rest with a fixed
+/// sequence of operations.
+///
+/// Every position of `block` is loaded from a clamped index of `tail` and
+/// masked out if it's beyond the tail, so neither the number of loads nor
+/// the branches depend on the length of the tail. It's only meant to
+/// avoid timing differences leaking into adjacent code, MurmurHash3 is not
+/// a cryptographic hash and this is not a security guarantee for it.
+#[cfg(feature = "ct")]
+#[inline]
+fn read_tail_ct(tail: &[u8], block: &mut [u8]) {
+    let len = tail.len();
+    let src: &[u8] = if len == 0 { &[0] } else { tail };
+    let last = src.len() - 1;
+    for (i, b) in block.iter_mut().enumerate() {
+        let mask = ((i < len) as u8).wrapping_neg();
+        *b = src[i.min(last)] & mask;
+    }
+}
+
+mod hash128_x86 {
+    use crate::raw::fmix32;
+
+    const C1: u32 = 0x239b961b;
+    const C2: u32 = 0xab0e9789;
+    const C3: u32 = 0x38b34ae5;
+    const C4: u32 = 0xa1e38b93;
+
+    /// Gets the 128-bit MurmurHash3 sum of data with the x86 variant.
+    ///
+    /// The x86 variant is a different algorithm from `murmurhash3_x64_128`
+    /// and produces different results, it's optimized for 32 bit
+    /// platforms. The four 32-bit words of the result are packed into two
+    /// `u64` in little endian, so the result has the same bytes as the
+    /// output of the reference implementation on little endian machines.
+    pub fn murmurhash3_x86_128(bytes: &[u8], seed: u32) -> (u64, u64) {
+        let mut h = [seed; 4];
+        let mut blocks = bytes.chunks_exact(16);
+        for block in &mut blocks {
+            let mut k = [0; 4];
+            for (i, k) in k.iter_mut().enumerate() {
+                let mut word = [0; 4];
+                word.copy_from_slice(&block[i * 4..i * 4 + 4]);
+                *k = u32::from_le_bytes(word);
+            }
+            h = feed(h, k);
+        }
+
+        // Missing bytes are zeros, and mixing a zero word is a no-op.
+        let tail = blocks.remainder();
+        let mut k = [0; 4];
+        for (i, b) in tail.iter().enumerate() {
+            k[i / 4] |= (*b as u32) << (8 * (i % 4));
+        }
+        h[0] ^= mix_k1(k[0]);
+        h[1] ^= mix_k2(k[1]);
+        h[2] ^= mix_k3(k[2]);
+        h[3] ^= mix_k4(k[3]);
+
+        for h in &mut h {
+            *h ^= bytes.len() as u32;
+        }
+        h = merge(h);
+        for h in &mut h {
+            *h = fmix32(*h);
+        }
+        h = merge(h);
+
+        (
+            h[0] as u64 | (h[1] as u64) << 32,
+            h[2] as u64 | (h[3] as u64) << 32,
+        )
+    }
+
+    #[inline]
+    fn mix_k1(k: u32) -> u32 {
+        k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2)
+    }
+
+    #[inline]
+    fn mix_k2(k: u32) -> u32 {
+        k.wrapping_mul(C2).rotate_left(16).wrapping_mul(C3)
+    }
+
+    #[inline]
+    fn mix_k3(k: u32) -> u32 {
+        k.wrapping_mul(C3).rotate_left(17).wrapping_mul(C4)
+    }
+
+    #[inline]
+    fn mix_k4(k: u32) -> u32 {
+        k.wrapping_mul(C4).rotate_left(18).wrapping_mul(C1)
+    }
+
+    #[inline]
+    fn feed(mut h: [u32; 4], k: [u32; 4]) -> [u32; 4] {
+        h[0] ^= mix_k1(k[0]);
+        h[0] = h[0].rotate_left(19).wrapping_add(h[1]);
+        h[0] = h[0].wrapping_mul(5).wrapping_add(0x561ccd1b);
+
+        h[1] ^= mix_k2(k[1]);
+        h[1] = h[1].rotate_left(17).wrapping_add(h[2]);
+        h[1] = h[1].wrapping_mul(5).wrapping_add(0x0bcaa747);
+
+        h[2] ^= mix_k3(k[2]);
+        h[2] = h[2].rotate_left(15).wrapping_add(h[3]);
+        h[2] = h[2].wrapping_mul(5).wrapping_add(0x96cd1c35);
+
+        h[3] ^= mix_k4(k[3]);
+        h[3] = h[3].rotate_left(13).wrapping_add(h[0]);
+        h[3] = h[3].wrapping_mul(5).wrapping_add(0x32ac3b17);
+        h
+    }
+
+    #[inline]
+    fn merge(mut h: [u32; 4]) -> [u32; 4] {
+        h[0] = h[0]
+            .wrapping_add(h[1])
+            .wrapping_add(h[2])
+            .wrapping_add(h[3]);
+        h[1] = h[1].wrapping_add(h[0]);
+        h[2] = h[2].wrapping_add(h[0]);
+        h[3] = h[3].wrapping_add(h[0]);
+        h
+    }
+}
+
+/// Gets both the 32-bit and the 128-bit MurmurHash3 sums of data.
+///
+/// The results are the same as `murmurhash3_x86_32` and
+/// `murmurhash3_x64_128`. The two algorithms use different block sizes,
+/// constants and finalization, so no state or loop can be shared between
+/// them, the data is simply hashed twice.
+#[inline]
+pub fn murmurhash3_both(bytes: &[u8], seed: u32) -> (u32, (u64, u64)) {
+    (
+        murmurhash3_x86_32(bytes, seed),
+        murmurhash3_x64_128(bytes, seed),
+    )
+}
+
+/// A hash width in bits, for choosing the algorithm of `murmur_hash`.
+///
+/// Only `Bits<32>` and `Bits<128>` implement `Output`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bits<const BITS: usize>;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::Bits<32> {}
+    impl Sealed for super::Bits<128> {}
+}
+
+/// Maps a hash width to its algorithm and output, see `murmur_hash`.
+///
+/// The trait is sealed and can't be implemented outside of the crate.
+pub trait Output: sealed::Sealed {
+    /// The byte array of the hash, `[u8; BITS / 8]`.
+    type Out;
+
+    /// Gets the MurmurHash3 sum of data in little endian bytes.
+    fn hash(bytes: &[u8], seed: u32) -> Self::Out;
+}
+
+impl Output for Bits<32> {
+    type Out = [u8; 4];
+
+    #[inline]
+    fn hash(bytes: &[u8], seed: u32) -> [u8; 4] {
+        murmurhash3_x86_32(bytes, seed).to_le_bytes()
+    }
+}
+
+impl Output for Bits<128> {
+    type Out = [u8; 16];
+
+    #[inline]
+    fn hash(bytes: &[u8], seed: u32) -> [u8; 16] {
+        hash128::to_bytes(murmurhash3_x64_128(bytes, seed))
+    }
+}
+
+/// Gets the `BITS`-bit MurmurHash3 sum of data as `[u8; BITS / 8]`.
+///
+/// `BITS` picks the algorithm: 32 for `murmurhash3_x86_32` and 128 for
+/// `murmurhash3_x64_128`. Results are encoded in little endian, which is
+/// the same as `Hasher128::finish_bytes` for 128 bits.
+///
+/// ```
+/// let h: [u8; 4] = mur3::murmur_hash::<32>(b"hello", 0);
+/// assert_eq!(h, mur3::murmurhash3_x86_32(b"hello", 0).to_le_bytes());
+/// ```
+///
+/// Other widths are rejected at compile time:
+/// ```compile_fail
+/// let h = mur3::murmur_hash::<64>(b"hello", 0);
+/// ```
+#[inline]
+pub fn murmur_hash<const BITS: usize>(bytes: &[u8], seed: u32) -> <Bits<BITS> as Output>::Out
+where
+    Bits<BITS>: Output,
+{
+    <Bits<BITS> as Output>::hash(bytes, seed)
+}
+
+/// Known vectors of (seed, x86_32, x64_128 h1, x64_128 h2, key).
+const SELF_TEST_DATA: &[(u32, u32, u64, u64, &str)] = &[
+    (0x00, 0x00000000, 0x0000000000000000, 0x0000000000000000, ""),
+    (
+        0x00,
+        0x248bfa47,
+        0xcbd8a7b341bd9b02,
+        0x5b1e906a48ae1d19,
+        "hello",
+    ),
+    (0x01, 0x514e28b7, 0x4610abe56eff5cb5, 0x51622daa78f83583, ""),
+    (
+        0x01,
+        0xf50e1f30,
+        0x2a929de9c8f97b2f,
+        0x56a41d99af43a2db,
+        "19 Jan 2038 at 3:14:07 AM",
+    ),
+    (
+        0x2a,
+        0xc02d1434,
+        0x74f33c659cda5af7,
+        0x4ec7a891caf316f0,
+        "The quick brown fox jumps over the lazy dog.",
+    ),
+];
+
+/// Checks the hash functions against known vectors.
+///
+/// Both the one-shot functions and the hashers are checked. It returns
+/// false if any result mismatches, which indicates a miscompilation or
+/// a bug on current target. It's cheap, so it's recommended to call it
+/// once at startup before trusting the hashes on exotic targets.
+pub fn self_test() -> bool {
+    use core::hash::Hasher;
+
+    SELF_TEST_DATA.iter().all(|&(seed, h32, h1, h2, s)| {
+        let mut hasher32 = Hasher32::with_seed(seed);
+        hasher32.write(s.as_bytes());
+        let mut hasher128 = Hasher128::with_seed(seed);
+        hasher128.write(s.as_bytes());
+        murmurhash3_x86_32(s.as_bytes(), seed) == h32
+            && hasher32.finish32() == h32
+            && murmurhash3_x64_128(s.as_bytes(), seed) == (h1, h2)
+            && hasher128.finish128() == (h1, h2)
+    })
+}
+
+pub mod backend;
+mod build_hasher;
+#[cfg(feature = "digest")]
+mod digest_impl;
+#[cfg(feature = "ffi")]
+#[allow(unsafe_code)]
+pub mod ffi;
+mod ints;
+pub mod raw;
+mod rolling;
+mod sink;
+mod state;
+#[cfg(feature = "std")]
+mod tee;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use build_hasher::Murmur3BuildHasher128;
+#[cfg(feature = "digest")]
+pub use digest_impl::{Murmur3_128, Murmur3_32};
+pub use hash128::{
+    bloom_indices, combine128, expand_64, fast_range, hash_display_fmt, hash_value128, merge128,
+    murmurhash3_x64_128, murmurhash3_x64_128_aligned_info, murmurhash3_x64_128_bytes_be,
+    murmurhash3_x64_128_eq, murmurhash3_x64_128_finish, murmurhash3_x64_128_fixed,
+    murmurhash3_x64_128_from_bytes, murmurhash3_x64_128_iter, murmurhash3_x64_128_key,
+    murmurhash3_x64_128_limited, murmurhash3_x64_128_nonzero, murmurhash3_x64_128_of,
+    murmurhash3_x64_128_resume, murmurhash3_x64_128_ring, murmurhash3_x64_128_str,
+    murmurhash3_x64_128_u16le, murmurhash3_x64_128_u32le, murmurhash3_x64_128_vectored,
+    murmurhash3_x64_64, Hasher128, Hasher128Ext, Murmur128, Murmur128Hex, WriteReport,
+};
+#[cfg(feature = "rayon")]
+pub use hash128::{hash_chunks_parallel, murmurhash3_x64_128_par};
+#[cfg(feature = "alloc")]
+pub use hash128::{
+    hash_display, murmurhash3_x64_128_batch, murmurhash3_x64_128_batch_into,
+    murmurhash3_x64_128_hex, murmurhash3_x64_128_hex_upper, murmurhash3_x64_128_uuid,
+    murmurhash3_x64_128_uuid_v8, murmurhash3_x64_128_vec, suffix_hashes,
+};
+#[cfg(feature = "std")]
+pub use hash128::{
+    murmurhash3_x64_128_io_slices, murmurhash3_x64_128_mmap, murmurhash3_x64_128_path,
+};
+pub use hash128_x86::murmurhash3_x86_128;
+pub use hash32::{
+    hash_combine32, murmurhash3_x86_32, murmurhash3_x86_32_chained, murmurhash3_x86_32_long,
+    murmurhash3_x86_32_of, Hasher32,
+};
+pub use ints::{murmurhash3_x64_128_ints, ToLeBytes};
+pub use rolling::RollingMurmur128;
+pub use sink::Murmur128Sink;
+pub use state::{restore, Hasher128State, Hasher32State, HasherKind, InvalidState, RestoreError};
+#[cfg(feature = "std")]
+pub use tee::TeeHasher128;
+
+#[cfg(all(test, any(target_arch = "wasm32", feature = "portable")))]
+mod portable_tests {
+    use core::hash::Hasher;
+
+    #[test]
+    fn test_portable_path() {
+        let data = b"The quick brown fox jumps over the lazy dog.";
+        assert_eq!(super::murmurhash3_x86_32(data, 0x2a), 0xc02d1434);
+        assert_eq!(
+            super::murmurhash3_x64_128(data, 0x2a),
+            (0x74f33c659cda5af7, 0x4ec7a891caf316f0)
+        );
+
+        let mut hasher = super::Hasher128::with_seed(0x2a);
+        for chunk in data.chunks(7) {
+            hasher.write(chunk);
+        }
+        assert_eq!(hasher.finish128(), (0x74f33c659cda5af7, 0x4ec7a891caf316f0));
+    }
+}
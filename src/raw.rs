@@ -0,0 +1,32 @@
+//! Low level building blocks of the hash functions.
+//!
+//! These are the mixing primitives that the hash functions are composed
+//! of, for implementing custom variants, for example, with a different
+//! finalization. They are advanced and unstable APIs, misusing them, like
+//! feeding words in big endian or skipping the length, produces hashes
+//! that are incompatible with MurmurHash3.
+//!
+//! A 128-bit hash of a key whose length is a multiple of 16 can be
+//! composed as follows:
+//! ```
+//! use mur3::raw::{feed128, finalize128};
+//!
+//! let key = [7u8; 32];
+//! let (mut h1, mut h2) = (0, 0);
+//! for block in key.chunks(16) {
+//!     let k1 = u64::from_le_bytes([
+//!         block[0], block[1], block[2], block[3], block[4], block[5], block[6], block[7],
+//!     ]);
+//!     let k2 = u64::from_le_bytes([
+//!         block[8], block[9], block[10], block[11], block[12], block[13], block[14], block[15],
+//!     ]);
+//!     let h = feed128(h1, h2, k1, k2);
+//!     h1 = h.0;
+//!     h2 = h.1;
+//! }
+//! let h = finalize128(h1, h2, key.len() as u64);
+//! assert_eq!(h, mur3::murmurhash3_x64_128(&key, 0));
+//! ```
+
+pub use crate::hash128::{feed128, finalize128, finish_tail128, fmix64};
+pub use crate::hash32::{feed32, finish_tail32, fmix32};
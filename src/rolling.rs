@@ -0,0 +1,72 @@
+use crate::Hasher128;
+use core::hash::Hasher;
+
+/// Hashes a sliding window over a stream of bytes.
+///
+/// The window holds the last `W` pushed bytes, or all of them before `W`
+/// bytes are pushed. MurmurHash3 can't remove a byte from the front of a
+/// hashed sequence, so every step rehashes the whole window from a ring
+/// buffer, which takes O(W) time. The hash is the same as hashing the
+/// window contents with `murmurhash3_x64_128`.
+///
+/// ```
+/// let mut rolling = mur3::RollingMurmur128::<4>::new(0);
+/// for b in b"hello" {
+///     rolling.push(*b);
+/// }
+/// assert_eq!(rolling.hash(), mur3::murmurhash3_x64_128(b"ello", 0));
+/// ```
+#[derive(Clone)]
+pub struct RollingMurmur128<const W: usize> {
+    seed: u32,
+    buf: [u8; W],
+    // Position of the oldest byte once the window is full.
+    pos: usize,
+    len: usize,
+}
+
+impl<const W: usize> RollingMurmur128<W> {
+    /// Creates an empty window with given seed.
+    pub fn new(seed: u32) -> RollingMurmur128<W> {
+        RollingMurmur128 {
+            seed,
+            buf: [0; W],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    /// Pushes a byte into the window, evicting the oldest byte if the
+    /// window is full, and returns the hash of the new window.
+    pub fn push(&mut self, byte: u8) -> (u64, u64) {
+        if W == 0 {
+            return self.hash();
+        }
+        if self.len < W {
+            self.buf[self.len] = byte;
+            self.len += 1;
+        } else {
+            self.buf[self.pos] = byte;
+            self.pos = (self.pos + 1) % W;
+        }
+        self.hash()
+    }
+
+    /// Gets the hash of the bytes in the window.
+    pub fn hash(&self) -> (u64, u64) {
+        let mut hasher = Hasher128::with_seed(self.seed);
+        hasher.write(&self.buf[self.pos..self.len]);
+        hasher.write(&self.buf[..self.pos]);
+        hasher.finish128()
+    }
+
+    /// Gets the number of bytes in the window.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if no byte has been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
@@ -0,0 +1,68 @@
+use crate::Hasher128;
+use core::hash::Hasher;
+
+/// A sink that absorbs chunks of data and digests them into a 128-bit
+/// MurmurHash3 sum.
+///
+/// It's a thin wrapper of `Hasher128` with names that read well in data
+/// pipelines, the digest is the same as hashing all absorbed chunks
+/// concatenated.
+///
+/// ```
+/// let source: &[&[u8]] = &[b"hello", b" ", b"world"];
+/// let mut sink = mur3::Murmur128Sink::new(0);
+/// for chunk in source {
+///     sink.absorb(chunk);
+/// }
+/// assert_eq!(sink.digest(), mur3::murmurhash3_x64_128(b"hello world", 0));
+/// ```
+#[derive(Clone)]
+pub struct Murmur128Sink {
+    hasher: Hasher128,
+}
+
+impl Murmur128Sink {
+    /// Creates a sink with given seed.
+    #[inline]
+    pub fn new(seed: u32) -> Murmur128Sink {
+        Murmur128Sink {
+            hasher: Hasher128::with_seed(seed),
+        }
+    }
+
+    /// Absorbs a chunk of data.
+    #[inline]
+    pub fn absorb(&mut self, chunk: &[u8]) {
+        self.hasher.write(chunk);
+    }
+
+    /// Gets the 128-bit hash of all absorbed data.
+    ///
+    /// It doesn't consume the sink, more chunks can be absorbed after it.
+    #[inline]
+    pub fn digest(&self) -> (u64, u64) {
+        self.hasher.finish128()
+    }
+
+    /// Consumes the sink and returns the inner hasher.
+    #[inline]
+    pub fn into_inner(self) -> Hasher128 {
+        self.hasher
+    }
+}
+
+impl From<Hasher128> for Murmur128Sink {
+    #[inline]
+    fn from(hasher: Hasher128) -> Murmur128Sink {
+        Murmur128Sink { hasher }
+    }
+}
+
+impl<'a> Extend<&'a [u8]> for Murmur128Sink {
+    /// Absorbs all chunks in order.
+    fn extend<I: IntoIterator<Item = &'a [u8]>>(&mut self, chunks: I) {
+        for chunk in chunks {
+            self.absorb(chunk);
+        }
+    }
+}
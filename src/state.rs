@@ -0,0 +1,315 @@
+use crate::{Hasher128, Hasher32};
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{de::Error, de::Unexpected, Deserialize, Deserializer, Serialize};
+
+/// The error returned when converting an invalid state back to a hasher.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidState;
+
+impl fmt::Display for InvalidState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("buffered length exceeds block size")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidState {}
+
+#[cfg(feature = "serde")]
+fn deserialize_len<'de, D: Deserializer<'de>>(d: D, block: usize) -> Result<usize, D::Error> {
+    let len = usize::deserialize(d)?;
+    if len >= block {
+        return Err(D::Error::invalid_value(
+            Unexpected::Unsigned(len as u64),
+            &"a length less than block size",
+        ));
+    }
+    Ok(len)
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_len128<'de, D: Deserializer<'de>>(d: D) -> Result<usize, D::Error> {
+    deserialize_len(d, 16)
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_len32<'de, D: Deserializer<'de>>(d: D) -> Result<usize, D::Error> {
+    deserialize_len(d, 4)
+}
+
+/// The intermediate state of a `Hasher128`.
+///
+/// With `serde` feature enabled, the state can be serialized and
+/// deserialized, so in-progress hashes can be persisted and resumed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hasher128State {
+    /// The first half of the running hash.
+    pub h1: u64,
+    /// The second half of the running hash.
+    pub h2: u64,
+    /// Buffered bytes that are not enough for a block yet.
+    pub buf: [u8; 16],
+    /// Count of valid bytes in `buf`, must be less than 16.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_len128"))]
+    pub len: usize,
+    /// Count of bytes that have been mixed into the running hash.
+    pub consume: u64,
+}
+
+impl From<&Hasher128> for Hasher128State {
+    fn from(h: &Hasher128) -> Hasher128State {
+        Hasher128State {
+            h1: h.h1,
+            h2: h.h2,
+            buf: h.buf,
+            len: h.len,
+            consume: h.consume,
+        }
+    }
+}
+
+impl TryFrom<Hasher128State> for Hasher128 {
+    type Error = InvalidState;
+
+    fn try_from(s: Hasher128State) -> Result<Hasher128, InvalidState> {
+        if s.len >= 16 {
+            return Err(InvalidState);
+        }
+        Ok(Hasher128 {
+            h1: s.h1,
+            h2: s.h2,
+            buf: s.buf,
+            len: s.len,
+            consume: s.consume,
+            peeked: (0, 0),
+            dirty: true,
+        })
+    }
+}
+
+/// The intermediate state of a `Hasher32`.
+///
+/// With `serde` feature enabled, the state can be serialized and
+/// deserialized, so in-progress hashes can be persisted and resumed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hasher32State {
+    /// The running hash.
+    pub h: u32,
+    /// Buffered bytes that are not enough for a block yet.
+    pub buf: [u8; 4],
+    /// Count of valid bytes in `buf`, must be less than 4.
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_len32"))]
+    pub len: usize,
+    /// Count of bytes that have been mixed into the running hash.
+    pub consume: u64,
+}
+
+impl From<&Hasher32> for Hasher32State {
+    fn from(h: &Hasher32) -> Hasher32State {
+        Hasher32State {
+            h: h.h,
+            buf: h.buf,
+            len: h.len,
+            consume: h.consume,
+        }
+    }
+}
+
+impl TryFrom<Hasher32State> for Hasher32 {
+    type Error = InvalidState;
+
+    fn try_from(s: Hasher32State) -> Result<Hasher32, InvalidState> {
+        if s.len >= 4 {
+            return Err(InvalidState);
+        }
+        Ok(Hasher32 {
+            h: s.h,
+            buf: s.buf,
+            len: s.len,
+            consume: s.consume,
+        })
+    }
+}
+
+/// The version of the tagged snapshot format.
+const SNAPSHOT_VERSION: u8 = 1;
+/// The kind tag of a `Hasher32` snapshot.
+const KIND_32: u8 = 32;
+/// The kind tag of a `Hasher128` snapshot.
+const KIND_128: u8 = 128;
+
+/// The error returned when restoring a hasher from a tagged snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestoreError {
+    /// The blob is shorter than the snapshot it claims to be.
+    TooShort,
+    /// The blob is of an unsupported version of the format.
+    BadVersion,
+    /// The blob is of an unknown kind of hasher.
+    BadKind,
+    /// The buffered length is not less than the block size.
+    BadLen,
+}
+
+impl fmt::Display for RestoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RestoreError::TooShort => "snapshot is too short",
+            RestoreError::BadVersion => "unsupported snapshot version",
+            RestoreError::BadKind => "unknown hasher kind",
+            RestoreError::BadLen => "buffered length exceeds block size",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RestoreError {}
+
+/// A hasher restored from a tagged snapshot by `restore`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HasherKind {
+    /// A restored `Hasher32`.
+    Hasher32(Hasher32),
+    /// A restored `Hasher128`.
+    Hasher128(Hasher128),
+}
+
+impl Hasher128 {
+    /// Dumps the intermediate state into a self-describing byte blob.
+    ///
+    /// The blob is a version byte and a kind byte followed by the state,
+    /// all fields are encoded in little endian. It can be loaded by
+    /// `restore`.
+    pub fn snapshot_tagged(&self) -> [u8; 42] {
+        let mut bytes = [0; 42];
+        bytes[0] = SNAPSHOT_VERSION;
+        bytes[1] = KIND_128;
+        bytes[2..10].copy_from_slice(&self.h1.to_le_bytes());
+        bytes[10..18].copy_from_slice(&self.h2.to_le_bytes());
+        bytes[18..26].copy_from_slice(&self.consume.to_le_bytes());
+        // At most 15 bytes can be buffered, the last slot is used for len.
+        bytes[26..41].copy_from_slice(&self.buf[..15]);
+        bytes[41] = self.len as u8;
+        bytes
+    }
+
+    /// Dumps the intermediate state into a fixed byte blob.
+    ///
+    /// The blob is the one of `snapshot_tagged` without the version and
+    /// kind bytes.
+    #[deprecated(since = "0.1.0", note = "use `snapshot_tagged` instead")]
+    pub fn snapshot(&self) -> [u8; 40] {
+        self.snapshot_tagged()[2..].try_into().unwrap()
+    }
+
+    /// Restores a hasher from the blob generated by `snapshot`.
+    ///
+    /// Returns `None` if the blob is corrupted, that is the buffered
+    /// length is not less than 16.
+    #[deprecated(since = "0.1.0", note = "use `mur3::restore` instead")]
+    pub fn restore(bytes: &[u8; 40]) -> Option<Hasher128> {
+        let mut tagged = [0; 42];
+        tagged[0] = SNAPSHOT_VERSION;
+        tagged[1] = KIND_128;
+        tagged[2..].copy_from_slice(bytes);
+        match restore(&tagged) {
+            Ok(HasherKind::Hasher128(h)) => Some(h),
+            _ => None,
+        }
+    }
+}
+
+impl Hasher32 {
+    /// Dumps the intermediate state into a self-describing byte blob.
+    ///
+    /// The blob is a version byte and a kind byte followed by the state,
+    /// all fields are encoded in little endian. It can be loaded by
+    /// `restore`.
+    pub fn snapshot_tagged(&self) -> [u8; 18] {
+        let mut bytes = [0; 18];
+        bytes[0] = SNAPSHOT_VERSION;
+        bytes[1] = KIND_32;
+        bytes[2..6].copy_from_slice(&self.h.to_le_bytes());
+        bytes[6..14].copy_from_slice(&self.consume.to_le_bytes());
+        // At most 3 bytes can be buffered, the last slot is used for len.
+        bytes[14..17].copy_from_slice(&self.buf[..3]);
+        bytes[17] = self.len as u8;
+        bytes
+    }
+}
+
+/// Restores a hasher from the blob generated by `snapshot_tagged`.
+///
+/// The version and the kind of the blob are checked, so a blob of a
+/// `Hasher32` can't be restored as a `Hasher128` by accident. Bytes after
+/// the blob are ignored.
+///
+/// ```
+/// use std::hash::Hasher;
+///
+/// let mut hasher = mur3::Hasher128::with_seed(0);
+/// hasher.write(b"hello");
+/// let blob = hasher.snapshot_tagged();
+/// match mur3::restore(&blob) {
+///     Ok(mur3::HasherKind::Hasher128(h)) => assert_eq!(h.finish128(), hasher.finish128()),
+///     _ => unreachable!(),
+/// }
+/// ```
+pub fn restore(bytes: &[u8]) -> Result<HasherKind, RestoreError> {
+    if bytes.len() < 2 {
+        return Err(RestoreError::TooShort);
+    }
+    if bytes[0] != SNAPSHOT_VERSION {
+        return Err(RestoreError::BadVersion);
+    }
+    match bytes[1] {
+        KIND_128 => {
+            let blob = bytes.get(2..42).ok_or(RestoreError::TooShort)?;
+            let len = blob[39] as usize;
+            if len >= 16 {
+                return Err(RestoreError::BadLen);
+            }
+            let read_u64 = |pos: usize| u64::from_le_bytes(blob[pos..pos + 8].try_into().unwrap());
+            let mut buf = [0; 16];
+            buf[..15].copy_from_slice(&blob[24..39]);
+            Ok(HasherKind::Hasher128(Hasher128 {
+                h1: read_u64(0),
+                h2: read_u64(8),
+                buf,
+                len,
+                consume: read_u64(16),
+                peeked: (0, 0),
+                dirty: true,
+            }))
+        }
+        KIND_32 => {
+            let blob = bytes.get(2..18).ok_or(RestoreError::TooShort)?;
+            let len = blob[15] as usize;
+            if len >= 4 {
+                return Err(RestoreError::BadLen);
+            }
+            let mut buf = [0; 4];
+            buf[..3].copy_from_slice(&blob[12..15]);
+            Ok(HasherKind::Hasher32(Hasher32 {
+                h: u32::from_le_bytes(blob[..4].try_into().unwrap()),
+                buf,
+                len,
+                consume: u64::from_le_bytes(blob[4..12].try_into().unwrap()),
+            }))
+        }
+        _ => Err(RestoreError::BadKind),
+    }
+}
+
+impl TryFrom<&[u8]> for HasherKind {
+    type Error = RestoreError;
+
+    fn try_from(bytes: &[u8]) -> Result<HasherKind, RestoreError> {
+        restore(bytes)
+    }
+}
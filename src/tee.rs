@@ -0,0 +1,56 @@
+use crate::Hasher128;
+use core::hash::Hasher;
+use std::io::{self, Write};
+
+/// A writer that hashes all bytes written through it to an inner writer.
+///
+/// Only the bytes accepted by the inner writer are hashed, so the hash
+/// always matches the forwarded data, even on short writes or errors.
+///
+/// ```
+/// use std::io::Write;
+///
+/// let mut tee = mur3::TeeHasher128::new(mur3::Hasher128::with_seed(0), Vec::new());
+/// tee.write_all(b"hello").unwrap();
+/// let (hasher, out) = tee.into_parts();
+/// assert_eq!(out, b"hello");
+/// assert_eq!(hasher.finish128(), mur3::murmurhash3_x64_128(b"hello", 0));
+/// ```
+pub struct TeeHasher128<W: Write> {
+    hasher: Hasher128,
+    writer: W,
+}
+
+impl<W: Write> TeeHasher128<W> {
+    /// Creates a tee that feeds `hasher` and forwards to `writer`.
+    pub fn new(hasher: Hasher128, writer: W) -> TeeHasher128<W> {
+        TeeHasher128 { hasher, writer }
+    }
+
+    /// Gets the hasher fed so far.
+    pub fn hasher(&self) -> &Hasher128 {
+        &self.hasher
+    }
+
+    /// Gets the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Consumes the tee and returns the hasher and the inner writer.
+    pub fn into_parts(self) -> (Hasher128, W) {
+        (self.hasher, self.writer)
+    }
+}
+
+impl<W: Write> Write for TeeHasher128<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.writer.write(buf)?;
+        self.hasher.write(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,138 @@
+//! Helpers for testing code built on the hashers.
+
+use crate::backend::{Backend, Scalar};
+use crate::{
+    murmur_hash, murmurhash3_x64_128, murmurhash3_x64_128_from_bytes, murmurhash3_x64_128_iter,
+    murmurhash3_x64_128_vectored, murmurhash3_x86_128, murmurhash3_x86_32, murmurhash3_x86_32_long,
+    Hasher128, Hasher32,
+};
+use core::hash::Hasher;
+
+/// Feeds data to a `Hasher128` in chunks split at given points.
+///
+/// The hash must not depend on how data is split, which is a common
+/// property to check in tests of code that streams data into hashers.
+///
+/// ```
+/// use mur3::testing::ChunkedFeeder;
+///
+/// let feeder = ChunkedFeeder::new(b"hello, world", 42);
+/// assert_eq!(feeder.hash(&[3, 3, 7]), mur3::murmurhash3_x64_128(b"hello, world", 42));
+/// feeder.check(&[1, 11]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ChunkedFeeder<'a> {
+    data: &'a [u8],
+    seed: u32,
+}
+
+impl<'a> ChunkedFeeder<'a> {
+    /// Creates a feeder of `data` with given seed.
+    pub fn new(data: &'a [u8], seed: u32) -> ChunkedFeeder<'a> {
+        ChunkedFeeder { data, seed }
+    }
+
+    /// Feeds data in chunks split at `splits` and returns the hash.
+    ///
+    /// Chunks are `data[..splits[0]]`, `data[splits[0]..splits[1]]`, ...,
+    /// and the rest of data. Repeated points produce empty chunks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `splits` is not sorted or any point exceeds the length of
+    /// data.
+    pub fn hash(&self, splits: &[usize]) -> (u64, u64) {
+        let mut hasher = Hasher128::with_seed(self.seed);
+        let mut start = 0;
+        for &end in splits {
+            assert!(
+                start <= end && end <= self.data.len(),
+                "invalid split point {} after {}, data length {}",
+                end,
+                start,
+                self.data.len()
+            );
+            hasher.write(&self.data[start..end]);
+            start = end;
+        }
+        hasher.write(&self.data[start..]);
+        hasher.finish128()
+    }
+
+    /// Feeds data in chunks split at `splits` and checks the hash against
+    /// the one-shot hash of data.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the split points and both hashes if they mismatch, or
+    /// if `splits` is invalid as described in `hash`.
+    pub fn check(&self, splits: &[usize]) -> (u64, u64) {
+        let chunked = self.hash(splits);
+        let expected = murmurhash3_x64_128(self.data, self.seed);
+        assert_eq!(
+            chunked,
+            expected,
+            "chunked hash mismatches, seed: {}, data length: {}, splits: {:?}",
+            self.seed,
+            self.data.len(),
+            splits
+        );
+        chunked
+    }
+}
+
+/// Checks all variants of the hashes against the C reference
+/// implementation.
+///
+/// The one-shot functions, the hashers fed at once and byte by byte, and
+/// the other entry points that must produce the same results are compared
+/// to the reference of all three algorithms. Returns false on any
+/// mismatch, so it can be used as a fuzz target directly:
+///
+/// ```ignore
+/// fuzz_target!(|data: &[u8]| {
+///     assert!(mur3::testing::differential_check(data, 0));
+/// });
+/// ```
+///
+/// # Panics
+///
+/// Panics if data is longer than `i32::MAX` bytes, which the reference
+/// implementation doesn't support.
+pub fn differential_check(bytes: &[u8], seed: u32) -> bool {
+    assert!(
+        bytes.len() <= i32::MAX as usize,
+        "data length {} is not supported by the reference implementation",
+        bytes.len()
+    );
+    let h32 = mur3_c::hash32(bytes, seed);
+    let h128 = mur3_c::hash128_64(bytes, seed);
+    let h128_86 = mur3_c::hash128_86(bytes, seed);
+
+    let mut hasher32 = Hasher32::with_seed(seed);
+    hasher32.write(bytes);
+    let mut bytewise32 = Hasher32::with_seed(seed);
+    let mut hasher128 = Hasher128::with_seed(seed);
+    hasher128.write(bytes);
+    let mut bytewise128 = Hasher128::with_seed(seed);
+    for &b in bytes {
+        bytewise32.write_u8(b);
+        bytewise128.write_u8(b);
+    }
+    let (first, second) = bytes.split_at(bytes.len() / 2);
+
+    murmurhash3_x86_32(bytes, seed) == h32
+        && murmurhash3_x86_32_long(bytes, seed) == h32
+        && hasher32.finish32() == h32
+        && bytewise32.finish32() == h32
+        && murmur_hash::<32>(bytes, seed) == h32.to_le_bytes()
+        && murmurhash3_x64_128(bytes, seed) == h128
+        && hasher128.finish128() == h128
+        && bytewise128.finish128() == h128
+        && Scalar.hash128(bytes, seed) == h128
+        && murmurhash3_x64_128_vectored(&[first, second], seed) == h128
+        && murmurhash3_x64_128_iter(bytes.chunks(7), seed) == h128
+        && murmurhash3_x64_128_from_bytes(bytes.iter().copied(), seed) == h128
+        && murmur_hash::<128>(bytes, seed) == hasher128.finish_bytes()
+        && murmurhash3_x86_128(bytes, seed) == h128_86
+}
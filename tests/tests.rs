@@ -1,5 +1,7 @@
 use core::hash::Hasher;
 use mur3::*;
+// Miri can't call foreign functions, so tests against the C implementation
+// are ignored under it.
 use mur3_c::*;
 use quickcheck_macros::quickcheck;
 
@@ -93,6 +95,56 @@ const DATA: &[(u32, u32, u64, u64, &str)] = &[
     ),
 ];
 
+// The x86 variant of 128-bit sums, (seed, h1, h2, str), where h1 and h2
+// pack the four 32-bit words in little endian.
+const DATA_X86_128: &[(u32, u64, u64, &str)] = &[
+    (0x00, 0x0000000000000000, 0x0000000000000000, ""),
+    (0x00, 0xdb91def72b2444a0, 0x9adb31b69adb31b6, "hello"),
+    (0x00, 0xb9b98a1e8b21605c, 0xeb5957c793273a83, "hello, world"),
+    (
+        0x00,
+        0x8d28ce425cea0ad4,
+        0x38ccaf8cb50613f0,
+        "19 Jan 2038 at 3:14:07 AM",
+    ),
+    (
+        0x00,
+        0x7dd6ed5e6cbb6099,
+        0x9b627b552bbf0fbb,
+        "The quick brown fox jumps over the lazy dog.",
+    ),
+    (0x01, 0x54d201b988c4adec, 0x54d201b954d201b9, ""),
+    (0x01, 0x1759b52feba4da84, 0x7c10bd197c10bd19, "hello"),
+    (0x01, 0xdfefa397a9de3b94, 0x32f08bd1535dd6d6, "hello, world"),
+    (
+        0x01,
+        0x0e16c38fbe7d31c4,
+        0xd190a47581b40541,
+        "19 Jan 2038 at 3:14:07 AM",
+    ),
+    (
+        0x01,
+        0x2b960f121171b7d1,
+        0x34873022218fc6b5,
+        "The quick brown fox jumps over the lazy dog.",
+    ),
+    (0x2a, 0x95c80cbaaf6d2cb6, 0x95c80cba95c80cba, ""),
+    (0x2a, 0x053404f69c4f9a01, 0x886f9b95886f9b95, "hello"),
+    (0x2a, 0x8094183bef8be0fc, 0xea66b8d474352732, "hello, world"),
+    (
+        0x2a,
+        0x84b4f1d65cb224f4,
+        0xf81aec20bb9b2815,
+        "19 Jan 2038 at 3:14:07 AM",
+    ),
+    (
+        0x2a,
+        0x5345c26111ab6efe,
+        0x9bd8c50befaa41a4,
+        "The quick brown fox jumps over the lazy dog.",
+    ),
+];
+
 #[test]
 fn test_strings() {
     for (seed, h32, h64_1, h64_2, s) in DATA {
@@ -125,7 +177,79 @@ fn test_strings() {
     }
 }
 
+// The `safe` build reads blocks differently, all paths must produce the
+// same results as the default build.
+#[cfg(feature = "safe")]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_safe_strings() {
+    for (seed, h32, h64_1, h64_2, s) in DATA {
+        let b = s.as_bytes();
+        let h = (*h64_1, *h64_2);
+        assert_eq!(murmurhash3_x64_128_of(b, *seed), h, "{}", s);
+        assert_eq!(murmurhash3_x64_128_iter(b.chunks(3), *seed), h, "{}", s);
+        assert_eq!(
+            murmurhash3_x64_128_from_bytes(b.iter().copied(), *seed),
+            h,
+            "{}",
+            s
+        );
+        assert_eq!(murmurhash3_x64_128_str(s, *seed), h, "{}", s);
+        assert_eq!(murmurhash3_x86_32_of(b, *seed), *h32, "{}", s);
+
+        let mut hasher = Hasher128::with_seed(*seed);
+        let mut hasher32 = Hasher32::with_seed(*seed);
+        for c in b.chunks(7) {
+            hasher.write(c);
+            hasher32.write(c);
+        }
+        assert_eq!(hasher.finish128(), h, "{}", s);
+        assert_eq!(hasher32.finish32(), *h32, "{}", s);
+
+        let (x1, x2) = hash128_86(b, *seed);
+        assert_eq!(murmurhash3_x86_128(b, *seed), (x1, x2), "{}", s);
+    }
+
+    let words: Vec<u32> = (0..37).map(|i| i * 0x01010101).collect();
+    let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+    assert_eq!(
+        murmurhash3_x64_128_u32le(&words, 42),
+        murmurhash3_x64_128(&bytes, 42)
+    );
+}
+
+// The generic APIs are instantiated here, with `safe` feature they must
+// still match the one-shot hash.
+#[cfg(feature = "safe")]
+#[test]
+fn test_safe_generics() {
+    let data = b"The quick brown fox jumps over the lazy dog.";
+    let h = murmurhash3_x64_128(data, 0);
+    let mut hasher = Hasher128::with_seed(0);
+    hasher.write_iter(data.chunks(3));
+    assert_eq!(hasher.finish128(), h);
+    assert_eq!(murmur_hash::<128>(data, 0), hasher.finish_bytes());
+    assert_eq!(
+        murmur_hash::<32>(data, 0),
+        murmurhash3_x86_32(data, 0).to_le_bytes()
+    );
+
+    let ints: Vec<u64> = (0..7).map(|i| i * 0x0101_0101_0101_0101).collect();
+    let bytes: Vec<u8> = ints.iter().flat_map(|i| i.to_le_bytes()).collect();
+    assert_eq!(
+        murmurhash3_x64_128_ints(&ints, 42),
+        murmurhash3_x64_128(&bytes, 42)
+    );
+    let ints: Vec<i16> = vec![-3, 1, 4];
+    let bytes: Vec<u8> = ints.iter().flat_map(|i| i.to_le_bytes()).collect();
+    assert_eq!(
+        murmurhash3_x64_128_ints(&ints, 42),
+        murmurhash3_x64_128(&bytes, 42)
+    );
+}
+
 #[quickcheck]
+#[cfg_attr(miri, ignore)]
 fn random_check_32(xs: Vec<u8>) -> bool {
     let func_res = murmurhash3_x86_32(&xs, 0);
     let mut hasher32 = Hasher32::with_seed(0);
@@ -135,17 +259,33 @@ fn random_check_32(xs: Vec<u8>) -> bool {
     func_res == hash_res && hash_res == c_res
 }
 
+/// Checks the detected backend against the scalar one in the same binary,
+/// so a backend enabled by CPU detection or `target-feature` flags can't
+/// change results. Every offset in a block is checked for alignment
+/// dependent paths.
+fn backends_agree(xs: &[u8], seed: u32) -> bool {
+    use mur3::backend::{self, Backend};
+
+    let detected = backend::detect();
+    (0..16.min(xs.len() + 1)).all(|i| {
+        let xs = &xs[i..];
+        detected.hash128(xs, seed) == backend::Scalar.hash128(xs, seed)
+    })
+}
+
 #[quickcheck]
+#[cfg_attr(miri, ignore)]
 fn random_check_128(xs: Vec<u8>) -> bool {
     let func_res = murmurhash3_x64_128(&xs, 0);
     let mut hasher = Hasher128::with_seed(0);
     hasher.write(&xs);
     let hash_res = hasher.finish128();
     let c_res = hash128_64(&xs, 0);
-    func_res == hash_res && hash_res == c_res
+    func_res == hash_res && hash_res == c_res && backends_agree(&xs, 0)
 }
 
 #[quickcheck]
+#[cfg_attr(miri, ignore)]
 fn random_check_32_seed(xs: Vec<u8>, seed: u32) -> bool {
     let func_res = murmurhash3_x86_32(&xs, seed);
     let mut hasher32 = Hasher32::with_seed(seed);
@@ -156,16 +296,24 @@ fn random_check_32_seed(xs: Vec<u8>, seed: u32) -> bool {
 }
 
 #[quickcheck]
+#[cfg_attr(miri, ignore)]
 fn random_check_128_seed(xs: Vec<u8>, seed: u32) -> bool {
     let func_res = murmurhash3_x64_128(&xs, seed);
     let mut hasher = Hasher128::with_seed(seed);
     hasher.write(&xs);
     let hash_res = hasher.finish128();
     let c_res = hash128_64(&xs, seed);
-    func_res == hash_res && hash_res == c_res
+    func_res == hash_res && hash_res == c_res && backends_agree(&xs, seed)
+}
+
+#[quickcheck]
+fn random_check_128_ring(first: Vec<u8>, second: Vec<u8>, seed: u32) -> bool {
+    let joined = [&first[..], &second[..]].concat();
+    murmurhash3_x64_128_ring(&first, &second, seed) == murmurhash3_x64_128(&joined, seed)
 }
 
 #[quickcheck]
+#[cfg_attr(miri, ignore)]
 fn random_check_32_chunks(xs: Vec<Vec<u8>>, seed: u32) -> bool {
     let mut all_bytes = vec![];
     for c in &xs {
@@ -182,6 +330,7 @@ fn random_check_32_chunks(xs: Vec<Vec<u8>>, seed: u32) -> bool {
 }
 
 #[quickcheck]
+#[cfg_attr(miri, ignore)]
 fn random_check_128_chunks(xs: Vec<Vec<u8>>, seed: u32) -> bool {
     let mut all_bytes = vec![];
     for c in &xs {
@@ -194,5 +343,1989 @@ fn random_check_128_chunks(xs: Vec<Vec<u8>>, seed: u32) -> bool {
     }
     let hash_res = hasher.finish128();
     let c_res = hash128_64(&all_bytes, seed);
-    func_res == hash_res && hash_res == c_res
+    func_res == hash_res && hash_res == c_res && backends_agree(&all_bytes, seed)
+}
+
+#[test]
+fn test_expand_64() {
+    let mut out = [0; 8];
+    expand_64(b"hello, world", 0, 8, &mut out);
+    let mut again = [0; 8];
+    expand_64(b"hello, world", 0, 8, &mut again);
+    assert_eq!(out, again);
+    for i in 0..out.len() {
+        for j in i + 1..out.len() {
+            assert_ne!(out[i], out[j], "{} {}", i, j);
+        }
+    }
+
+    // Only the first `count` values are written.
+    let mut partial = [0; 8];
+    expand_64(b"hello, world", 0, 3, &mut partial);
+    assert_eq!(partial[..3], out[..3]);
+    assert_eq!(partial[3..], [0; 5]);
+
+    let mut other = [0; 8];
+    expand_64(b"hello, world", 1, 8, &mut other);
+    assert_ne!(out, other);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_snapshot_restore() {
+    let data: Vec<u8> = (0..200).collect();
+    for split in [0, 1, 15, 16, 17, 100, 200] {
+        let mut hasher = Hasher128::with_seed(42);
+        hasher.write(&data[..split]);
+        let snapshot = hasher.snapshot();
+        assert_eq!(snapshot[..], hasher.snapshot_tagged()[2..]);
+
+        let mut restored = Hasher128::restore(&snapshot).unwrap();
+        assert_eq!(restored.finish128(), hasher.finish128());
+        restored.write(&data[split..]);
+        assert_eq!(
+            restored.finish128(),
+            murmurhash3_x64_128(&data, 42),
+            "split: {}",
+            split
+        );
+    }
+
+    let mut corrupted = Hasher128::with_seed(0).snapshot();
+    corrupted[39] = 16;
+    assert!(Hasher128::restore(&corrupted).is_none());
+}
+
+#[test]
+fn test_limited() {
+    let data: Vec<u8> = (0..100).collect();
+    for max_blocks in 0..8 {
+        let prefix = &data[..(max_blocks * 16).min(data.len())];
+        assert_eq!(
+            murmurhash3_x64_128_limited(&data, max_blocks, 42),
+            murmurhash3_x64_128(prefix, 42),
+            "max_blocks: {}",
+            max_blocks
+        );
+    }
+    assert_eq!(
+        murmurhash3_x64_128_limited(&data, usize::MAX, 42),
+        murmurhash3_x64_128(&data, 42)
+    );
+}
+
+#[test]
+fn test_state_conversion() {
+    use std::convert::TryFrom;
+
+    let mut hasher = Hasher32::with_seed(42);
+    hasher.write(b"hello");
+    let mut state = Hasher32State::from(&hasher);
+    let mut restored = Hasher32::try_from(state).unwrap();
+    restored.write(b", world");
+    assert_eq!(restored.finish32(), murmurhash3_x86_32(b"hello, world", 42));
+
+    state.len = 4;
+    assert_eq!(Hasher32::try_from(state).err(), Some(InvalidState));
+    let mut state = Hasher128State::from(&Hasher128::with_seed(0));
+    state.len = 16;
+    assert_eq!(Hasher128::try_from(state).err(), Some(InvalidState));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_state_serde() {
+    use std::convert::TryFrom;
+
+    let data: Vec<u8> = (0..100).collect();
+    let mut hasher = Hasher128::with_seed(42);
+    hasher.write(&data[..37]);
+    let bytes = bincode::serialize(&Hasher128State::from(&hasher)).unwrap();
+    let state: Hasher128State = bincode::deserialize(&bytes).unwrap();
+    let mut restored = Hasher128::try_from(state).unwrap();
+    restored.write(&data[37..]);
+    assert_eq!(restored.finish128(), murmurhash3_x64_128(&data, 42));
+
+    let mut hasher = Hasher32::with_seed(42);
+    hasher.write(&data[..37]);
+    let bytes = bincode::serialize(&Hasher32State::from(&hasher)).unwrap();
+    let state: Hasher32State = bincode::deserialize(&bytes).unwrap();
+    let mut restored = Hasher32::try_from(state).unwrap();
+    restored.write(&data[37..]);
+    assert_eq!(restored.finish32(), murmurhash3_x86_32(&data, 42));
+
+    let mut state = Hasher128State::from(&Hasher128::with_seed(0));
+    state.len = 16;
+    let bytes = bincode::serialize(&state).unwrap();
+    assert!(bincode::deserialize::<Hasher128State>(&bytes).is_err());
+    let mut state = Hasher32State::from(&Hasher32::with_seed(0));
+    state.len = 4;
+    let bytes = bincode::serialize(&state).unwrap();
+    assert!(bincode::deserialize::<Hasher32State>(&bytes).is_err());
+}
+
+#[quickcheck]
+fn random_check_write_str(s: String, seed: u32) -> bool {
+    let mut hasher = Hasher128::with_seed(seed);
+    hasher.write_str(&s);
+    let mut bytes_hasher = Hasher128::with_seed(seed);
+    bytes_hasher.write(s.as_bytes());
+
+    let mut hasher32 = Hasher32::with_seed(seed);
+    hasher32.write_str(&s);
+    let mut bytes_hasher32 = Hasher32::with_seed(seed);
+    bytes_hasher32.write(s.as_bytes());
+
+    hasher.finish128() == bytes_hasher.finish128()
+        && hasher32.finish32() == bytes_hasher32.finish32()
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_ffi() {
+    use std::ffi::c_void;
+    use std::os::raw::c_int;
+
+    extern "C" {
+        fn mur3_murmurhash3_x86_32(key: *const c_void, len: c_int, seed: u32, out: *mut c_void);
+        fn mur3_murmurhash3_x64_128(key: *const c_void, len: c_int, seed: u32, out: *mut c_void);
+    }
+
+    fn ffi_hash32(bytes: &[u8], seed: u32) -> u32 {
+        let mut output: u32 = 0;
+        unsafe {
+            mur3_murmurhash3_x86_32(
+                bytes.as_ptr() as _,
+                bytes.len() as i32,
+                seed,
+                &mut output as *mut u32 as _,
+            );
+        }
+        output
+    }
+
+    fn ffi_hash128_64(bytes: &[u8], seed: u32) -> (u64, u64) {
+        let mut output: (u64, u64) = (0, 0);
+        unsafe {
+            mur3_murmurhash3_x64_128(
+                bytes.as_ptr() as _,
+                bytes.len() as i32,
+                seed,
+                &mut output as *mut (u64, u64) as _,
+            );
+        }
+        output
+    }
+
+    for (seed, _, _, _, s) in DATA {
+        let bytes = s.as_bytes();
+        assert_eq!(ffi_hash32(bytes, *seed), hash32(bytes, *seed), "{}", s);
+        assert_eq!(
+            ffi_hash128_64(bytes, *seed),
+            hash128_64(bytes, *seed),
+            "{}",
+            s
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_fork() {
+    let mut parent = Hasher128::with_seed(42);
+    parent.write(b"parent");
+    let children = parent.fork(&[1, 2]);
+    assert_eq!(children.len(), 2);
+
+    let mut results = vec![];
+    for (mut child, id) in children.into_iter().zip([1u32, 2]) {
+        child.write(b"suffix");
+        let mut expected = parent.clone();
+        expected.write(&id.to_le_bytes());
+        expected.write(b"suffix");
+        assert_eq!(child.finish128(), expected.finish128(), "id: {}", id);
+        results.push(child.finish128());
+    }
+    assert_ne!(results[0], results[1]);
+}
+
+#[test]
+fn test_finish_bytes() {
+    for (seed, _, h64_1, h64_2, s) in DATA {
+        let mut hasher = Hasher128::with_seed(*seed);
+        hasher.write(s.as_bytes());
+        let bytes = hasher.finish_bytes();
+        assert_eq!(bytes[..8], h64_1.to_le_bytes());
+        assert_eq!(bytes[8..], h64_2.to_le_bytes());
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_hex() {
+    let hex = murmurhash3_x64_128_hex(b"hello", 0);
+    assert_eq!(hex, "029bbd41b3a7d8cb191dae486a901e5b");
+    assert_eq!(
+        murmurhash3_x64_128_hex_upper(b"hello", 0),
+        "029BBD41B3A7D8CB191DAE486A901E5B"
+    );
+
+    for (seed, _, _, _, s) in DATA {
+        let mut hasher = Hasher128::with_seed(*seed);
+        hasher.write(s.as_bytes());
+        let hex = hasher.finish_hex();
+        assert_eq!(hex.len(), 32);
+        assert_eq!(hex, murmurhash3_x64_128_hex(s.as_bytes(), *seed));
+        assert_eq!(hasher.finish_hex_upper(), hex.to_uppercase());
+        assert_eq!(
+            u128::from_str_radix(&hex, 16).unwrap(),
+            u128::from_be_bytes(hasher.finish_bytes())
+        );
+    }
+}
+
+#[test]
+fn test_write_tagged() {
+    let mut a = Hasher128::with_seed(0);
+    a.write_tagged(1, b"x");
+    let mut b = Hasher128::with_seed(0);
+    b.write_tagged(2, b"x");
+    assert_ne!(a.finish128(), b.finish128());
+    assert_eq!(a.finish128(), murmurhash3_x64_128(b"\x01x", 0));
+}
+
+#[test]
+fn test_combine128() {
+    let a = murmurhash3_x64_128(b"hello", 0);
+    let b = murmurhash3_x64_128(b"world", 0);
+    let c = murmurhash3_x64_128(b"!", 0);
+    assert_eq!(combine128(a, b), combine128(a, b));
+    assert_ne!(combine128(a, b), combine128(b, a));
+    assert_ne!(
+        combine128(combine128(a, b), c),
+        combine128(a, combine128(b, c))
+    );
+    assert_ne!(combine128(a, b), murmurhash3_x64_128(b"helloworld", 0));
+}
+
+#[test]
+fn test_murmur_hash() {
+    for (seed, h32, h64_1, h64_2, s) in DATA {
+        let h: [u8; 4] = murmur_hash::<32>(s.as_bytes(), *seed);
+        assert_eq!(u32::from_le_bytes(h), *h32);
+        assert_eq!(h, murmurhash3_x86_32(s.as_bytes(), *seed).to_le_bytes());
+
+        let h: [u8; 16] = murmur_hash::<128>(s.as_bytes(), *seed);
+        assert_eq!(h[..8], h64_1.to_le_bytes());
+        assert_eq!(h[8..], h64_2.to_le_bytes());
+    }
+}
+
+#[quickcheck]
+fn random_check_128_iter(xs: Vec<Vec<u8>>, seed: u32) -> bool {
+    let mut all_bytes = vec![];
+    for c in &xs {
+        all_bytes.extend_from_slice(c);
+    }
+    let func_res = murmurhash3_x64_128(&all_bytes, seed);
+    let iter_res = murmurhash3_x64_128_iter(xs.iter().map(|x| x.as_slice()), seed);
+    func_res == iter_res
+}
+
+#[test]
+fn test_aligned_info() {
+    let data: Vec<u8> = (0..64).collect();
+    for len in 0..=data.len() {
+        let (h, aligned) = murmurhash3_x64_128_aligned_info(&data[..len], 42);
+        assert_eq!(h, murmurhash3_x64_128(&data[..len], 42));
+        assert_eq!(aligned, len % 16 == 0, "len: {}", len);
+    }
+}
+
+#[test]
+fn test_hasher_eq() {
+    let data: Vec<u8> = (0..50).collect();
+    let mut a = Hasher128::with_seed(42);
+    a.write(&data);
+    let mut a32 = Hasher32::with_seed(42);
+    a32.write(&data);
+    for chunk_size in 1..20 {
+        let mut b = Hasher128::with_seed(42);
+        let mut b32 = Hasher32::with_seed(42);
+        for chunk in data.chunks(chunk_size) {
+            b.write(chunk);
+            b32.write(chunk);
+        }
+        assert!(a == b, "chunk size: {}", chunk_size);
+        assert!(a32 == b32, "chunk size: {}", chunk_size);
+    }
+
+    // Stale bytes in the buffer don't matter.
+    let mut b = Hasher128::with_seed(42);
+    b.write(&[0xff; 15]);
+    b.write(&[0xff; 1]);
+    b.write(&data[..1]);
+    let mut c = Hasher128::with_seed(42);
+    c.write(&[0xff; 16]);
+    c.write(&data[..1]);
+    assert!(b == c);
+
+    let mut b = Hasher128::with_seed(42);
+    b.write(&data[..49]);
+    assert!(a != b);
+    let mut b = Hasher128::with_seed(43);
+    b.write(&data);
+    assert!(a != b);
+    let mut b32 = Hasher32::with_seed(42);
+    b32.write(&data[1..]);
+    assert!(a32 != b32);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_suffix_hashes() {
+    let data = b"19 Jan 2038 at 3:14:07 AM";
+    let mut out = vec![];
+    suffix_hashes(data, 42, &mut out);
+    assert_eq!(out.len(), data.len() + 1);
+    for (i, h) in out.iter().enumerate() {
+        assert_eq!(*h, murmurhash3_x64_128(&data[i..], 42), "suffix: {}", i);
+    }
+    assert_eq!(out[data.len()], murmurhash3_x64_128(b"", 42));
+}
+
+// Too slow under Miri, and it has no unsafe code paths of its own.
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_fast_range() {
+    for n in [1, 2, 7, 10, 1000, u64::MAX] {
+        for i in 0..1000u32 {
+            assert!(fast_range(&i.to_le_bytes(), 0, n) < n, "n: {}", n);
+        }
+    }
+    assert_eq!(fast_range(b"hello", 0, 0), 0);
+
+    let mut buckets = [0; 10];
+    for i in 0..100_000u32 {
+        buckets[fast_range(&i.to_le_bytes(), 42, 10) as usize] += 1;
+    }
+    for (i, count) in buckets.iter().enumerate() {
+        assert!(*count > 9_000 && *count < 11_000, "bucket {}: {}", i, count);
+    }
+}
+
+// Runs against the `ct` tail with `--features ct`.
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_all_tail_lengths() {
+    let data: Vec<u8> = (0..64u8).map(|i| i.wrapping_mul(37) ^ 0x5a).collect();
+    for seed in [0, 1, 0x2a, u32::MAX] {
+        // Every tail length, both with and without full blocks before it.
+        for len in 0..=data.len() {
+            let bytes = &data[..len];
+            assert_eq!(
+                murmurhash3_x86_32(bytes, seed),
+                hash32(bytes, seed),
+                "len: {}, seed: {}",
+                len,
+                seed
+            );
+            let mut hasher32 = Hasher32::with_seed(seed);
+            hasher32.write(bytes);
+            assert_eq!(hasher32.finish32(), hash32(bytes, seed));
+
+            assert_eq!(
+                murmurhash3_x64_128(bytes, seed),
+                hash128_64(bytes, seed),
+                "len: {}, seed: {}",
+                len,
+                seed
+            );
+            let mut hasher = Hasher128::with_seed(seed);
+            hasher.write(bytes);
+            assert_eq!(hasher.finish128(), hash128_64(bytes, seed));
+        }
+    }
+}
+
+#[test]
+fn test_str() {
+    let (seed, _, h64_1, h64_2, s) = DATA[1];
+    assert_eq!(s, "hello");
+    assert_eq!(murmurhash3_x64_128_str("hello", seed), (h64_1, h64_2));
+    for (seed, _, h64_1, h64_2, s) in DATA {
+        assert_eq!(murmurhash3_x64_128_str(s, *seed), (*h64_1, *h64_2));
+    }
+}
+
+#[test]
+fn test_hash_display_fmt() {
+    assert_eq!(hash_display_fmt(&42u32, 0), murmurhash3_x64_128(b"42", 0));
+    let s = format_args!("{}, {}", "hello", "world").to_string();
+    assert_eq!(
+        hash_display_fmt(&s, 1),
+        murmurhash3_x64_128(b"hello, world", 1)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_hash_display() {
+    assert_eq!(hash_display(&42u32, 0), murmurhash3_x64_128(b"42", 0));
+    assert_eq!(hash_display(&-1.5f64, 0), hash_display_fmt(&-1.5f64, 0));
+    assert_eq!(hash_display("hello", 7), hash_display_fmt("hello", 7));
+}
+
+#[test]
+fn test_fixed() {
+    fn check<const N: usize>(seed: u32) {
+        let mut key = [0; N];
+        for (i, b) in key.iter_mut().enumerate() {
+            *b = i as u8 ^ 0xa5;
+        }
+        assert_eq!(
+            murmurhash3_x64_128_fixed(&key, seed),
+            murmurhash3_x64_128(&key[..], seed),
+            "N: {}, seed: {}",
+            N,
+            seed
+        );
+    }
+    for seed in [0, 1, 0x2a] {
+        check::<0>(seed);
+        check::<8>(seed);
+        check::<15>(seed);
+        check::<16>(seed);
+        check::<17>(seed);
+        check::<32>(seed);
+    }
+}
+
+#[test]
+fn test_feed_block() {
+    use core::convert::TryInto;
+    let data: Vec<u8> = (0..67u8).collect();
+    for (seed, _, _, _, _) in DATA {
+        let mut hasher = Hasher128::with_seed(*seed);
+        let mut blocks = data.chunks_exact(16);
+        for block in &mut blocks {
+            let k1 = u64::from_le_bytes(block[..8].try_into().unwrap());
+            let k2 = u64::from_le_bytes(block[8..].try_into().unwrap());
+            hasher.feed_block(k1, k2);
+        }
+        hasher.write(blocks.remainder());
+        assert_eq!(hasher.finish128(), murmurhash3_x64_128(&data, *seed));
+    }
+}
+
+#[test]
+fn test_hash_value128() {
+    #[derive(Hash)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    let mut hasher = Hasher128::with_seed(3);
+    hasher.write_u32(1);
+    hasher.write_u32(2);
+    let expected = hasher.finish128();
+    assert_eq!(hash_value128(&(1u32, 2u32), 3), expected);
+    assert_eq!(hash_value128(&Point { x: 1, y: 2 }, 3), expected);
+    assert_ne!(hash_value128(&Point { x: 2, y: 1 }, 3), expected);
+}
+
+#[test]
+fn test_auto_traits() {
+    fn assert_send_sync<T: Send + Sync + Unpin>() {}
+
+    assert_send_sync::<Hasher128>();
+    assert_send_sync::<Hasher32>();
+}
+
+#[test]
+fn test_self_test() {
+    assert!(self_test());
+}
+
+#[test]
+fn test_with_seed_bytes() {
+    let finish = |mut hasher: Hasher128| {
+        hasher.write(b"hello");
+        hasher.finish128()
+    };
+    let expected = finish(Hasher128::with_seed(42));
+    assert_eq!(
+        finish(Hasher128::with_seed_bytes(42u32.to_le_bytes())),
+        expected
+    );
+    assert_eq!(
+        finish(Hasher128::with_seed_bytes_be(42u32.to_be_bytes())),
+        expected
+    );
+    assert_eq!(
+        finish(Hasher128::with_seed_bytes_be(42u32.to_le_bytes())),
+        finish(Hasher128::with_seed(42u32.swap_bytes()))
+    );
+    assert_ne!(
+        finish(Hasher128::with_seed_bytes_be(42u32.to_le_bytes())),
+        expected
+    );
+}
+
+#[cfg(target_endian = "big")]
+#[test]
+fn test_big_endian() {
+    for (seed, h32, h64_1, h64_2, s) in DATA {
+        assert_eq!(murmurhash3_x86_32(s.as_bytes(), *seed), *h32, "{}", s);
+        assert_eq!(
+            murmurhash3_x64_128(s.as_bytes(), *seed),
+            (*h64_1, *h64_2),
+            "{}",
+            s
+        );
+        let mut hasher = Hasher128::with_seed(*seed);
+        for chunk in s.as_bytes().chunks(3) {
+            hasher.write(chunk);
+        }
+        assert_eq!(hasher.finish128(), (*h64_1, *h64_2), "{}", s);
+        let mut hasher = Hasher32::with_seed(*seed);
+        for chunk in s.as_bytes().chunks(3) {
+            hasher.write(chunk);
+        }
+        assert_eq!(hasher.finish32(), *h32, "{}", s);
+    }
+}
+
+#[test]
+fn test_raw() {
+    use mur3::raw::*;
+
+    let key: Vec<u8> = (0..48u8).collect();
+    for (seed, _, _, _, _) in DATA {
+        let mut h = *seed;
+        for block in key.chunks(4) {
+            let k = u32::from_le_bytes([block[0], block[1], block[2], block[3]]);
+            h = feed32(h, k);
+        }
+        h = fmix32(h ^ key.len() as u32);
+        assert_eq!(h, murmurhash3_x86_32(&key, *seed));
+
+        let (mut h1, mut h2) = (*seed as u64, *seed as u64);
+        for block in key.chunks(16) {
+            let mut k = [0; 2];
+            for (i, k) in k.iter_mut().enumerate() {
+                let mut word = [0; 8];
+                word.copy_from_slice(&block[i * 8..i * 8 + 8]);
+                *k = u64::from_le_bytes(word);
+            }
+            let res = feed128(h1, h2, k[0], k[1]);
+            h1 = res.0;
+            h2 = res.1;
+        }
+        h1 ^= key.len() as u64;
+        h2 ^= key.len() as u64;
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+        h1 = fmix64(h1);
+        h2 = fmix64(h2);
+        h1 = h1.wrapping_add(h2);
+        h2 = h2.wrapping_add(h1);
+        assert_eq!((h1, h2), murmurhash3_x64_128(&key, *seed));
+    }
+}
+
+#[test]
+fn test_murmurhash3_both() {
+    for (seed, h32, h64_1, h64_2, s) in DATA {
+        assert_eq!(
+            murmurhash3_both(s.as_bytes(), *seed),
+            (*h32, (*h64_1, *h64_2)),
+            "{}",
+            s
+        );
+    }
+}
+
+#[quickcheck]
+fn random_check_both(xs: Vec<u8>, seed: u32) -> bool {
+    murmurhash3_both(&xs, seed)
+        == (
+            murmurhash3_x86_32(&xs, seed),
+            murmurhash3_x64_128(&xs, seed),
+        )
+}
+
+#[test]
+fn test_write_fill_buffer() {
+    let data: Vec<u8> = (0..40u8).collect();
+    for (seed, _, _, _, _) in DATA {
+        for buffered in 1..16 {
+            // The second write exactly fills the buffer, leaving nothing.
+            let mut hasher = Hasher128::with_seed(*seed);
+            hasher.write(&data[..buffered]);
+            hasher.write(&data[buffered..16]);
+            hasher.write(&data[16..]);
+            assert_eq!(hasher.finish128(), murmurhash3_x64_128(&data, *seed));
+        }
+        for buffered in 1..4 {
+            let mut hasher = Hasher32::with_seed(*seed);
+            hasher.write(&data[..buffered]);
+            hasher.write(&data[buffered..4]);
+            hasher.write(&data[4..]);
+            assert_eq!(hasher.finish32(), murmurhash3_x86_32(&data, *seed));
+        }
+    }
+}
+
+#[cfg(target_pointer_width = "32")]
+#[test]
+fn test_32bit_platform() {
+    for (seed, _, h64_1, h64_2, s) in DATA {
+        assert_eq!(
+            murmurhash3_x64_128(s.as_bytes(), *seed),
+            (*h64_1, *h64_2),
+            "{}",
+            s
+        );
+        let mut hasher = Hasher128::with_seed(*seed);
+        for chunk in s.as_bytes().chunks(5) {
+            hasher.write(chunk);
+        }
+        assert_eq!(hasher.finish128(), (*h64_1, *h64_2), "{}", s);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_batch_into() {
+    let keys: Vec<&str> = DATA.iter().map(|d| d.4).collect();
+    let expected = murmurhash3_x64_128_batch(&keys, 42);
+    for (h, k) in expected.iter().zip(&keys) {
+        assert_eq!(*h, murmurhash3_x64_128(k.as_bytes(), 42), "{}", k);
+    }
+
+    let mut out = Vec::with_capacity(keys.len() * 2);
+    murmurhash3_x64_128_batch_into(&keys[..3], 42, &mut out);
+    assert_eq!(out, expected[..3]);
+    murmurhash3_x64_128_batch_into(&keys[3..], 42, &mut out);
+    assert_eq!(out, expected);
+}
+
+#[quickcheck]
+fn random_check_u32le(xs: Vec<u32>, seed: u32) -> bool {
+    let bytes: Vec<u8> = xs.iter().flat_map(|w| w.to_le_bytes()).collect();
+    murmurhash3_x64_128_u32le(&xs, seed) == murmurhash3_x64_128(&bytes, seed)
+}
+
+#[quickcheck]
+fn random_check_u16le(xs: Vec<u16>, seed: u32) -> bool {
+    let bytes: Vec<u8> = xs.iter().flat_map(|w| w.to_le_bytes()).collect();
+    murmurhash3_x64_128_u16le(&xs, seed) == murmurhash3_x64_128(&bytes, seed)
+}
+
+#[test]
+fn test_u16le() {
+    let s = "hello, world";
+    let utf16: Vec<u16> = s.encode_utf16().collect();
+    let bytes: Vec<u8> = s.bytes().flat_map(|b| [b, 0]).collect();
+    assert_eq!(
+        murmurhash3_x64_128_u16le(&utf16, 42),
+        murmurhash3_x64_128(&bytes, 42)
+    );
+    assert_eq!(
+        murmurhash3_x64_128_u32le(&[0x04030201, 0x08070605], 42),
+        murmurhash3_x64_128(&[1, 2, 3, 4, 5, 6, 7, 8], 42)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_vec() {
+    for (seed, _, _, _, s) in DATA {
+        let v = murmurhash3_x64_128_vec(s.as_bytes(), *seed);
+        assert_eq!(v.len(), 16);
+        let mut hasher = Hasher128::with_seed(*seed);
+        hasher.write_str(s);
+        assert_eq!(v, hasher.finish_bytes(), "{}", s);
+    }
+}
+
+#[test]
+fn test_write_after_finish() {
+    let mut hasher = Hasher128::with_seed(0);
+    hasher.write(b"hel");
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(b"hel", 0));
+    hasher.write(b"lo");
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(b"hello", 0));
+    hasher.write(b", world");
+    assert_eq!(hasher.finish128(), hasher.finish128());
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(b"hello, world", 0));
+
+    let mut hasher = Hasher32::with_seed(0);
+    hasher.write(b"hel");
+    assert_eq!(hasher.finish32(), murmurhash3_x86_32(b"hel", 0));
+    hasher.write(b"lo");
+    assert_eq!(hasher.finish32(), murmurhash3_x86_32(b"hello", 0));
+}
+
+#[test]
+fn test_x64_64() {
+    for (seed, _, h64_1, _, s) in DATA {
+        assert_eq!(murmurhash3_x64_64(s.as_bytes(), *seed), *h64_1, "{}", s);
+        let mut hasher = Hasher128::with_seed(*seed);
+        hasher.write_str(s);
+        assert_eq!(hasher.finish64(), hasher.finish128().0, "{}", s);
+        assert_eq!(hasher.finish64(), hasher.finish(), "{}", s);
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par() {
+    let keys: Vec<Vec<u8>> = (0..1000u32)
+        .map(|i| i.to_le_bytes().repeat(i as usize % 17))
+        .collect();
+    let expected: Vec<_> = keys.iter().map(|k| murmurhash3_x64_128(k, 42)).collect();
+    assert_eq!(murmurhash3_x64_128_par(&keys, 42), expected);
+}
+
+#[test]
+fn test_rolling() {
+    fn check<const W: usize>(data: &[u8]) {
+        let mut rolling = RollingMurmur128::<W>::new(42);
+        assert_eq!(rolling.hash(), murmurhash3_x64_128(b"", 42));
+        for i in 0..data.len() {
+            let window = &data[(i + 1).saturating_sub(W)..=i];
+            assert_eq!(rolling.push(data[i]), murmurhash3_x64_128(window, 42));
+            assert_eq!(rolling.len(), window.len());
+        }
+    }
+    let data: Vec<u8> = (0..100u8).map(|i| i.wrapping_mul(31)).collect();
+    check::<0>(&data);
+    check::<1>(&data);
+    check::<7>(&data);
+    check::<16>(&data);
+    check::<48>(&data);
+}
+
+#[test]
+fn test_reset_with_seed() {
+    let mut hasher = Hasher128::with_seed(1);
+    hasher.write(b"hello, world");
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(b"hello, world", 1));
+    hasher.reset_with_seed(2);
+    assert!(hasher == Hasher128::with_seed(2));
+    hasher.write(b"hello, world");
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(b"hello, world", 2));
+}
+
+#[test]
+fn test_bytes_written() {
+    let mut hasher128 = Hasher128::with_seed(0);
+    let mut hasher32 = Hasher32::with_seed(0);
+    assert_eq!(hasher128.bytes_written(), 0);
+    assert_eq!(hasher32.bytes_written(), 0);
+    let mut total = 0;
+    for len in [3, 0, 14, 1, 40, 7] {
+        let data = vec![0xab; len];
+        hasher128.write(&data);
+        hasher32.write(&data);
+        total += len as u64;
+        assert_eq!(hasher128.bytes_written(), total);
+        assert_eq!(hasher32.bytes_written(), total);
+    }
+}
+
+#[test]
+fn test_hasher128_ext() {
+    fn feed(hasher: &mut dyn Hasher128Ext) -> (u64, u64) {
+        hasher.write(b"hello");
+        assert_eq!(hasher.finish(), hasher.finish128().0);
+        hasher.finish128()
+    }
+    let mut hasher = Hasher128::with_seed(42);
+    assert_eq!(feed(&mut hasher), murmurhash3_x64_128(b"hello", 42));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_uuid() {
+    for (seed, _, _, _, s) in DATA {
+        let uuid = murmurhash3_x64_128_uuid(s.as_bytes(), *seed);
+        let groups: Vec<&str> = uuid.split('-').collect();
+        assert_eq!(
+            groups.iter().map(|g| g.len()).collect::<Vec<_>>(),
+            [8, 4, 4, 4, 12]
+        );
+        assert_eq!(
+            groups.concat(),
+            murmurhash3_x64_128_hex(s.as_bytes(), *seed),
+            "{}",
+            s
+        );
+
+        let v8 = murmurhash3_x64_128_uuid_v8(s.as_bytes(), *seed);
+        assert_eq!(v8.len(), 36);
+        assert_eq!(&v8[14..15], "8", "{}", v8);
+        assert!("89ab".contains(&v8[19..20]), "{}", v8);
+        for (i, (a, b)) in uuid.chars().zip(v8.chars()).enumerate() {
+            if i != 14 && i != 19 {
+                assert_eq!(a, b, "{} {}", uuid, v8);
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_small_inputs() {
+    let data: Vec<u8> = (1..=16u8).map(|i| i.wrapping_mul(0x9d)).collect();
+    for seed in [0, 1, 0x2a, 0xdeadbeef, u32::MAX] {
+        for len in 0..=16 {
+            let key = &data[..len];
+            assert_eq!(murmurhash3_x86_32(key, seed), hash32(key, seed), "{}", len);
+            assert_eq!(
+                murmurhash3_x64_128(key, seed),
+                hash128_64(key, seed),
+                "{}",
+                len
+            );
+        }
+    }
+}
+
+#[test]
+fn test_write_iter() {
+    let chunks: Vec<Vec<u8>> = (0..20u8).map(|i| vec![i; i as usize % 7]).collect();
+    let all = chunks.concat();
+    let mut hasher = Hasher128::with_seed(42);
+    hasher.write_iter(chunks.iter().map(|c| c.as_slice()));
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(&all, 42));
+
+    let mut hasher = Hasher128::with_seed(42);
+    hasher.extend(chunks.iter().map(|c| c.as_slice()));
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(&all, 42));
+}
+
+#[test]
+fn test_as_ref() {
+    let s = String::from("hello, world");
+    let v = s.clone().into_bytes();
+    let a = b"hello, world";
+    let expected128 = murmurhash3_x64_128(s.as_bytes(), 42);
+    assert_eq!(murmurhash3_x64_128_of(&s, 42), expected128);
+    assert_eq!(murmurhash3_x64_128_of(&v, 42), expected128);
+    assert_eq!(murmurhash3_x64_128_of(a, 42), expected128);
+    assert_eq!(murmurhash3_x64_128_of(s.clone(), 42), expected128);
+    let expected32 = murmurhash3_x86_32(s.as_bytes(), 42);
+    assert_eq!(murmurhash3_x86_32_of(&s, 42), expected32);
+    assert_eq!(murmurhash3_x86_32_of(v, 42), expected32);
+    assert_eq!(murmurhash3_x86_32_of(a, 42), expected32);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_tee_hasher() {
+    use std::io::{self, Write};
+
+    let data: Vec<u8> = (0..100u8).collect();
+    let mut tee = TeeHasher128::new(Hasher128::with_seed(42), Vec::new());
+    for chunk in data.chunks(9) {
+        tee.write_all(chunk).unwrap();
+    }
+    tee.flush().unwrap();
+    assert_eq!(tee.hasher().finish128(), murmurhash3_x64_128(&data, 42));
+    let (hasher, out) = tee.into_parts();
+    assert_eq!(out, data);
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(&data, 42));
+
+    // Only the bytes accepted by the inner writer are hashed.
+    let mut buf = [0; 10];
+    let mut tee = TeeHasher128::new(Hasher128::with_seed(42), &mut buf[..]);
+    assert_eq!(
+        tee.write_all(&data).unwrap_err().kind(),
+        io::ErrorKind::WriteZero
+    );
+    assert_eq!(
+        tee.hasher().finish128(),
+        murmurhash3_x64_128(&data[..10], 42)
+    );
+}
+
+#[test]
+fn test_finish_low32() {
+    for (seed, _, h64_1, _, s) in DATA {
+        let mut hasher = Hasher128::with_seed(*seed);
+        hasher.write_str(s);
+        assert_eq!(hasher.finish_low32(), *h64_1 as u32, "{}", s);
+        assert_eq!(hasher.finish_low32(), hasher.finish() as u32, "{}", s);
+    }
+}
+
+#[cfg(feature = "testing")]
+#[quickcheck]
+#[cfg_attr(miri, ignore)]
+fn random_check_chunked_feeder(data: Vec<u8>, mut splits: Vec<usize>, seed: u32) -> bool {
+    use mur3::testing::ChunkedFeeder;
+
+    for p in &mut splits {
+        *p %= data.len() + 1;
+    }
+    splits.sort_unstable();
+    let feeder = ChunkedFeeder::new(&data, seed);
+    feeder.check(&splits) == hash128_64(&data, seed)
+}
+
+#[cfg(feature = "testing")]
+#[test]
+#[should_panic(expected = "invalid split point")]
+fn test_chunked_feeder_unsorted() {
+    mur3::testing::ChunkedFeeder::new(b"hello", 0).hash(&[3, 2]);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_differential_check() {
+    use mur3::testing::differential_check;
+
+    for (seed, _, _, _, s) in DATA {
+        assert!(differential_check(s.as_bytes(), *seed), "{}", s);
+    }
+    let data: Vec<u8> = (0..1000).map(|i| (i * 13) as u8).collect();
+    for len in [0, 1, 15, 16, 17, 100, 1000] {
+        assert!(differential_check(&data[..len], 42), "{}", len);
+    }
+}
+
+#[test]
+fn test_resume() {
+    let data: Vec<u8> = (0..77u8).collect();
+    for (seed, _, _, _, _) in DATA {
+        let (h1, h2) = (*seed as u64, *seed as u64);
+        let (h1, h2) = murmurhash3_x64_128_resume(&data[..32], h1, h2, 0);
+        let (h1, h2) = murmurhash3_x64_128_resume(&data[32..64], h1, h2, 32);
+        assert_eq!(
+            murmurhash3_x64_128_finish(&data[64..], data.len() as u64, h1, h2),
+            murmurhash3_x64_128(&data, *seed)
+        );
+        let (h1, h2) = murmurhash3_x64_128_resume(&data[..64], *seed as u64, *seed as u64, 0);
+        assert_eq!(
+            murmurhash3_x64_128_finish(&[], 64, h1, h2),
+            murmurhash3_x64_128(&data[..64], *seed)
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "not a multiple of 16")]
+fn test_resume_unaligned() {
+    murmurhash3_x64_128_resume(&[0; 17], 0, 0, 0);
+}
+
+#[test]
+#[should_panic(expected = "consumed length 8 is not a multiple of 16")]
+fn test_resume_unaligned_consumed() {
+    murmurhash3_x64_128_resume(&[0; 16], 0, 0, 8);
+}
+
+#[test]
+fn test_raw_finalize128() {
+    use core::convert::TryInto;
+    use mur3::raw::*;
+
+    let key: Vec<u8> = (0..64u8).map(|i| i ^ 0x5a).collect();
+    for (seed, _, _, _, _) in DATA {
+        for len in [0, 16, 48, 64] {
+            let (mut h1, mut h2) = (*seed as u64, *seed as u64);
+            for block in key[..len].chunks(16) {
+                let k1 = u64::from_le_bytes(block[..8].try_into().unwrap());
+                let k2 = u64::from_le_bytes(block[8..].try_into().unwrap());
+                let res = feed128(h1, h2, k1, k2);
+                h1 = res.0;
+                h2 = res.1;
+            }
+            assert_eq!(
+                finalize128(h1, h2, len as u64),
+                murmurhash3_x64_128(&key[..len], *seed),
+                "{}",
+                len
+            );
+        }
+    }
+}
+
+#[test]
+fn test_fmt_write() {
+    use core::fmt::Write;
+
+    let mut hasher = Hasher128::with_seed(42);
+    write!(hasher, "a{}c", 12345).unwrap();
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(b"a12345c", 42));
+
+    let mut hasher = Hasher32::with_seed(42);
+    write!(hasher, "a{}c", 12345).unwrap();
+    assert_eq!(hasher.finish32(), murmurhash3_x86_32(b"a12345c", 42));
+}
+
+#[test]
+fn test_bloom_indices() {
+    for (seed, _, h64_1, h64_2, s) in DATA {
+        let h = murmurhash3_x64_128(s.as_bytes(), *seed);
+        for (k, m) in [(0, 1), (1, 1), (3, 64), (7, 1000), (16, u64::MAX)] {
+            let indices: Vec<u64> = bloom_indices(h, k, m).collect();
+            assert_eq!(indices.len(), k);
+            assert_eq!(indices, bloom_indices(h, k, m).collect::<Vec<_>>());
+            for (i, idx) in indices.iter().enumerate() {
+                assert!(*idx < m, "{} {}", idx, m);
+                let expected = h64_1.wrapping_add((i as u64).wrapping_mul(*h64_2)) % m;
+                assert_eq!(*idx, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_dyn_hasher() {
+    fn feed(hasher: &mut dyn Hasher, data: &[u8]) -> u64 {
+        for chunk in data.chunks(5) {
+            hasher.write(chunk);
+        }
+        hasher.write_u32(0xdeadbeef);
+        hasher.finish()
+    }
+
+    let data: Vec<u8> = (0..100u8).collect();
+    for len in [0, 1, 3, 4, 15, 16, 17, 64, 100] {
+        let data = &data[..len];
+        let mut direct = Hasher128::with_seed(42);
+        for chunk in data.chunks(5) {
+            direct.write(chunk);
+        }
+        direct.write_u32(0xdeadbeef);
+        let mut dynamic = Hasher128::with_seed(42);
+        assert_eq!(feed(&mut dynamic, data), direct.finish(), "{}", len);
+        assert_eq!(dynamic.finish128(), direct.finish128(), "{}", len);
+
+        let mut direct = Hasher32::with_seed(42);
+        for chunk in data.chunks(5) {
+            direct.write(chunk);
+        }
+        direct.write_u32(0xdeadbeef);
+        let mut dynamic = Hasher32::with_seed(42);
+        assert_eq!(feed(&mut dynamic, data), direct.finish(), "{}", len);
+        assert_eq!(dynamic.finish32(), direct.finish32(), "{}", len);
+    }
+}
+
+#[test]
+fn test_write_u8() {
+    for (seed, h32, h64_1, h64_2, s) in DATA {
+        let mut hasher128 = Hasher128::with_seed(*seed);
+        let mut hasher32 = Hasher32::with_seed(*seed);
+        for b in s.bytes() {
+            hasher128.write_u8(b);
+            hasher32.write_u8(b);
+        }
+        assert_eq!(hasher128.finish128(), (*h64_1, *h64_2), "{}", s);
+        assert_eq!(hasher32.finish32(), *h32, "{}", s);
+
+        // Mixed with slices, so single bytes land at every buffer offset.
+        let mut hasher128 = Hasher128::with_seed(*seed);
+        let mut hasher32 = Hasher32::with_seed(*seed);
+        for (i, chunk) in s.as_bytes().chunks(3).enumerate() {
+            if i % 2 == 0 {
+                hasher128.write(chunk);
+                hasher32.write(chunk);
+            } else {
+                for b in chunk {
+                    hasher128.write_u8(*b);
+                    hasher32.write_u8(*b);
+                }
+            }
+        }
+        assert_eq!(hasher128.finish128(), (*h64_1, *h64_2), "{}", s);
+        assert_eq!(hasher32.finish32(), *h32, "{}", s);
+    }
+}
+
+#[test]
+fn test_consts() {
+    const BUF128: [u8; Hasher128::BLOCK_SIZE] = [0; Hasher128::BLOCK_SIZE];
+    const BUF32: [u8; Hasher32::BLOCK_SIZE] = [0; Hasher32::BLOCK_SIZE];
+    const BITS: u32 = Hasher128::DIGEST_BITS + Hasher32::DIGEST_BITS;
+    assert_eq!(BUF128.len(), 16);
+    assert_eq!(BUF32.len(), 4);
+    assert_eq!(BITS, 160);
+    assert_eq!(
+        Hasher128::DIGEST_BITS as usize / 8,
+        Hasher128::with_seed(0).finish_bytes().len()
+    );
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn test_digest() {
+    use digest::Digest;
+
+    for (seed, h32, _, _, s) in DATA {
+        let mut hasher = Hasher128::with_seed(*seed);
+        hasher.write_str(s);
+        let mut d = Murmur3_128::with_seed(*seed);
+        for chunk in s.as_bytes().chunks(3) {
+            d.update(chunk);
+        }
+        assert_eq!(d.finalize_reset()[..], hasher.finish_bytes(), "{}", s);
+        d.update(s.as_bytes());
+        assert_eq!(d.finalize()[..], hasher.finish_bytes(), "{}", s);
+
+        let mut d = Murmur3_32::with_seed(*seed);
+        d.update(s.as_bytes());
+        assert_eq!(d.finalize()[..], h32.to_le_bytes(), "{}", s);
+    }
+    assert_eq!(
+        Murmur3_128::digest(b"hello")[..],
+        murmur_hash::<128>(b"hello", 0)
+    );
+}
+
+#[test]
+fn test_consume_overflow() {
+    use core::convert::TryFrom;
+
+    let mut state = Hasher128State::from(&Hasher128::with_seed(42));
+    state.consume = u64::MAX - 15;
+    let mut hasher = Hasher128::try_from(state).unwrap();
+    hasher.write(&[7; 40]);
+    assert_eq!(hasher.bytes_written(), 24);
+    // Only the length wraps, the mixed blocks are the same as from a
+    // fresh hasher.
+    let mut fresh = Hasher128::with_seed(42);
+    fresh.write(&[7; 40]);
+    let mut state = Hasher128State::from(&fresh);
+    state.consume = state.consume.wrapping_add(u64::MAX - 15);
+    assert_eq!(
+        hasher.finish128(),
+        murmurhash3_x64_128_finish(&[7; 8], 24, state.h1, state.h2)
+    );
+    let wrapped = Hasher128::try_from(state).unwrap();
+    assert_eq!(hasher.finish128(), wrapped.finish128());
+
+    let mut state = Hasher32State::from(&Hasher32::with_seed(42));
+    state.consume = u64::MAX - 3;
+    let mut hasher = Hasher32::try_from(state).unwrap();
+    hasher.write(&[7; 10]);
+    hasher.write_u8(7);
+    assert_eq!(hasher.bytes_written(), 7);
+    let mut fresh = Hasher32::with_seed(42);
+    fresh.write(&[7; 11]);
+    let mut state = Hasher32State::from(&fresh);
+    state.consume = state.consume.wrapping_add(u64::MAX - 3);
+    let wrapped = Hasher32::try_from(state).unwrap();
+    assert_eq!(hasher.finish32(), wrapped.finish32());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_all_algorithms() {
+    for (seed, h32, h64_1, h64_2, s) in DATA {
+        assert_eq!(hash32(s.as_bytes(), *seed), *h32, "{}", s);
+        assert_eq!(hash128_64(s.as_bytes(), *seed), (*h64_1, *h64_2), "{}", s);
+    }
+    for (seed, h1, h2, s) in DATA_X86_128 {
+        assert_eq!(
+            murmurhash3_x86_128(s.as_bytes(), *seed),
+            (*h1, *h2),
+            "{}",
+            s
+        );
+        assert_eq!(hash128_86(s.as_bytes(), *seed), (*h1, *h2), "{}", s);
+    }
+    // Every seed and string of `DATA` is covered by the x86 variant.
+    assert_eq!(DATA.len(), DATA_X86_128.len());
+    for ((seed, _, _, _, s), (seed_x86, _, _, s_x86)) in DATA.iter().zip(DATA_X86_128) {
+        assert_eq!((seed, s), (seed_x86, s_x86));
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_x86_128_tail_lengths() {
+    let data: Vec<u8> = (0..48u8).map(|i| i.wrapping_mul(0x3b) ^ 0xff).collect();
+    for seed in [0, 0x2a, u32::MAX] {
+        for len in 0..=data.len() {
+            let key = &data[..len];
+            assert_eq!(
+                murmurhash3_x86_128(key, seed),
+                hash128_86(key, seed),
+                "{}",
+                len
+            );
+        }
+    }
+}
+
+#[quickcheck]
+#[cfg_attr(miri, ignore)]
+fn random_check_x86_128(xs: Vec<u8>, seed: u32) -> bool {
+    murmurhash3_x86_128(&xs, seed) == hash128_86(&xs, seed)
+}
+
+#[test]
+fn test_murmur128_hex() {
+    use core::fmt::{self, Write};
+
+    struct Buf {
+        data: [u8; 32],
+        len: usize,
+    }
+
+    impl Write for Buf {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            if end > self.data.len() {
+                return Err(fmt::Error);
+            }
+            self.data[self.len..end].copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    let h = murmurhash3_x64_128(b"hello", 0);
+    let mut buf = Buf {
+        data: [0; 32],
+        len: 0,
+    };
+    write!(buf, "{}", Murmur128Hex(h)).unwrap();
+    assert_eq!(&buf.data, b"029bbd41b3a7d8cb191dae486a901e5b");
+    buf.len = 0;
+    write!(buf, "{:x}", Murmur128Hex(h)).unwrap();
+    assert_eq!(&buf.data, b"029bbd41b3a7d8cb191dae486a901e5b");
+    buf.len = 0;
+    write!(buf, "{:X}", Murmur128Hex(h)).unwrap();
+    assert_eq!(&buf.data, b"029BBD41B3A7D8CB191DAE486A901E5B");
+}
+
+#[quickcheck]
+fn random_check_from_bytes(xs: Vec<u8>, seed: u32) -> bool {
+    murmurhash3_x64_128_from_bytes(xs.iter().copied(), seed) == murmurhash3_x64_128(&xs, seed)
+}
+
+#[test]
+fn test_from_bytes() {
+    for (seed, _, h64_1, h64_2, s) in DATA {
+        assert_eq!(
+            murmurhash3_x64_128_from_bytes(s.bytes(), *seed),
+            (*h64_1, *h64_2),
+            "{}",
+            s
+        );
+    }
+}
+
+#[test]
+fn test_merge128() {
+    let parts: Vec<(u64, u64)> = DATA.iter().map(|d| (d.2, d.3)).collect();
+    assert_eq!(merge128(&parts), merge128(&parts));
+    let mut bytes = vec![];
+    for (h1, h2) in &parts {
+        bytes.extend_from_slice(&h1.to_le_bytes());
+        bytes.extend_from_slice(&h2.to_le_bytes());
+    }
+    assert_eq!(merge128(&parts), murmurhash3_x64_128(&bytes, 0));
+
+    let mut swapped = parts.clone();
+    swapped.swap(1, 2);
+    assert_ne!(merge128(&parts), merge128(&swapped));
+    assert_ne!(merge128(&parts[..2]), merge128(&parts[..3]));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_hash_chunks_parallel() {
+    let data: Vec<u8> = (0..10000u32).map(|i| (i * 7) as u8).collect();
+    for chunk_size in [1, 16, 100, 4096, 20000] {
+        let parts: Vec<_> = data
+            .chunks(chunk_size)
+            .map(|c| murmurhash3_x64_128(c, 42))
+            .collect();
+        assert_eq!(
+            hash_chunks_parallel(&data, chunk_size, 42),
+            merge128(&parts),
+            "{}",
+            chunk_size
+        );
+    }
+    assert_ne!(
+        hash_chunks_parallel(&data, 100, 42),
+        murmurhash3_x64_128(&data, 42)
+    );
+}
+
+#[test]
+fn test_x86_32_chained() {
+    let ns: &[u8] = b"namespace";
+    let key: &[u8] = b"key";
+    assert_eq!(
+        murmurhash3_x86_32_chained(&[ns, key], 42),
+        murmurhash3_x86_32(key, murmurhash3_x86_32(ns, 42))
+    );
+    assert_eq!(
+        murmurhash3_x86_32_chained(&[key], 42),
+        murmurhash3_x86_32(key, 42)
+    );
+    assert_eq!(murmurhash3_x86_32_chained(&[], 42), 42);
+    assert_ne!(
+        murmurhash3_x86_32_chained(&[b"ab", b"c"], 42),
+        murmurhash3_x86_32_chained(&[b"a", b"bc"], 42)
+    );
+}
+
+#[test]
+fn test_write_length_prefixed() {
+    let hash = |fields: &[&[u8]]| {
+        let mut hasher = Hasher128::with_seed(42);
+        for f in fields {
+            hasher.write_length_prefixed(f);
+        }
+        hasher.finish128()
+    };
+    assert_ne!(hash(&[b"ab", b"c"]), hash(&[b"a", b"bc"]));
+
+    let mut hasher = Hasher128::with_seed(42);
+    hasher.write(&3u64.to_le_bytes());
+    hasher.write(b"abc");
+    assert_eq!(hash(&[b"abc"]), hasher.finish128());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_murmurhash3_x64_128_mmap() {
+    let data: Vec<u8> = (0..3 * 4096 + 100).map(|i| (i * 7) as u8).collect();
+    for len in [0, 15, 16, 4095, 4096, 4097, 4096 + 31, data.len() - 1] {
+        for offset in [0, 1] {
+            let input = &data[offset..offset + len];
+            assert_eq!(
+                murmurhash3_x64_128_mmap(input, 42),
+                murmurhash3_x64_128(input, 42),
+                "len {} offset {}",
+                len,
+                offset
+            );
+        }
+    }
+}
+
+#[test]
+fn test_murmur128_key() {
+    for (seed, _, h64_1, h64_2, s) in DATA {
+        let key = murmurhash3_x64_128_key(s.as_bytes(), *seed);
+        assert_eq!(key, Murmur128(*h64_1, *h64_2));
+        assert_eq!(<(u64, u64)>::from(key), (*h64_1, *h64_2));
+        let mut hasher = Hasher128::with_seed(*seed);
+        hasher.write_str(s);
+        let wide = u128::from(key);
+        assert_eq!(wide, u128::from_le_bytes(hasher.finish_bytes()));
+        assert_eq!(Murmur128::from(wide), key);
+    }
+
+    assert!(Murmur128(1, 0) > Murmur128(0, u64::MAX));
+    assert!(Murmur128(1, 1) > Murmur128(1, 0));
+    let mut keys = vec![Murmur128(2, 0), Murmur128(1, 5), Murmur128(1, 3)];
+    keys.sort();
+    assert_eq!(keys, [Murmur128(1, 3), Murmur128(1, 5), Murmur128(2, 0)]);
+
+    let h = murmurhash3_x64_128(b"hello", 0);
+    assert_eq!(
+        format!("{:?}", Murmur128::from(h)),
+        "Murmur128(029bbd41b3a7d8cb191dae486a901e5b)"
+    );
+}
+
+#[test]
+fn test_hasher128_aligned_chunks() {
+    let buf: Vec<u8> = (0..4 * 4096 + 64).map(|i| (i * 31 + 7) as u8).collect();
+    let aligned = buf.as_ptr().align_offset(16);
+    for offset in [aligned, aligned + 1, aligned + 8] {
+        for chunk in [16, 4096, 4096 + 3] {
+            let data = &buf[offset..offset + 4 * 4096];
+            let mut hasher = Hasher128::with_seed(42);
+            for c in data.chunks(chunk) {
+                hasher.write(c);
+            }
+            assert_eq!(
+                hasher.finish128(),
+                murmurhash3_x64_128(data, 42),
+                "offset {} chunk {}",
+                offset - aligned,
+                chunk
+            );
+        }
+    }
+}
+
+#[test]
+fn test_x86_32_long_length() {
+    use std::convert::TryFrom;
+
+    for (seed, h32, _, _, s) in DATA {
+        assert_eq!(murmurhash3_x86_32_long(s.as_bytes(), *seed), *h32, "{}", s);
+    }
+
+    // A hasher that has consumed 4 GiB plus 4 bytes, without feeding them.
+    let state = |consume| Hasher32State {
+        h: 42,
+        buf: *b"ab\0\0",
+        len: 2,
+        consume,
+    };
+    let big = Hasher32::try_from(state((1 << 32) + 4)).unwrap();
+    let small = Hasher32::try_from(state(4)).unwrap();
+    assert_eq!(big.bytes_written(), (1 << 32) + 6);
+    // The length is truncated to 32 bits by default.
+    assert_eq!(big.finish32(), small.finish32());
+    assert_eq!(small.finish32_long(), small.finish32());
+    assert_ne!(big.finish32_long(), big.finish32());
+    // The high half is folded into the low half.
+    let folded = Hasher32::try_from(state(4 ^ 1)).unwrap();
+    assert_eq!(big.finish32_long(), folded.finish32());
+}
+
+#[test]
+fn test_nonzero_seed() {
+    use std::num::NonZeroU32;
+
+    let seed = NonZeroU32::new(5).unwrap();
+    let mut a = Hasher128::with_nonzero_seed(seed);
+    let mut b = Hasher128::with_seed(5);
+    assert_eq!(a.finish128(), b.finish128());
+    a.write(b"hello");
+    b.write(b"hello");
+    assert_eq!(a.finish128(), b.finish128());
+    assert_eq!(
+        murmurhash3_x64_128_nonzero(b"hello", seed),
+        murmurhash3_x64_128(b"hello", 5)
+    );
+}
+
+#[test]
+fn test_murmurhash3_x64_128_eq() {
+    for (seed, _, h64_1, h64_2, s) in DATA {
+        let expected = (*h64_1, *h64_2);
+        assert!(
+            murmurhash3_x64_128_eq(s.as_bytes(), *seed, expected),
+            "{}",
+            s
+        );
+        assert!(!murmurhash3_x64_128_eq(
+            s.as_bytes(),
+            *seed,
+            (*h64_1 ^ 1, *h64_2)
+        ));
+        assert!(!murmurhash3_x64_128_eq(
+            s.as_bytes(),
+            *seed,
+            (*h64_1, *h64_2 ^ 1)
+        ));
+    }
+    assert!(!murmurhash3_x64_128_eq(
+        b"hello",
+        1,
+        murmurhash3_x64_128(b"hello", 0)
+    ));
+}
+
+#[cfg(feature = "trace")]
+#[test]
+fn test_trace_write() {
+    let data: Vec<u8> = (0..48).collect();
+    let mut expected = vec![];
+    let (mut h1, mut h2) = (42, 42);
+    for (i, block) in data.chunks(16).enumerate() {
+        let h = murmurhash3_x64_128_resume(block, h1, h2, 16 * i as u64);
+        h1 = h.0;
+        h2 = h.1;
+        expected.push(h);
+    }
+
+    let mut trace = vec![];
+    let mut hasher = Hasher128::with_seed(42);
+    hasher.trace_write(&data, &mut |h1, h2| trace.push((h1, h2)));
+    assert_eq!(trace, expected);
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(&data, 42));
+
+    // Blocks completed across writes are reported too.
+    let mut trace = vec![];
+    let mut hasher = Hasher128::with_seed(42);
+    hasher.write(&data[..5]);
+    hasher.trace_write(&data[5..40], &mut |h1, h2| trace.push((h1, h2)));
+    hasher.trace_write(&data[40..], &mut |h1, h2| trace.push((h1, h2)));
+    assert_eq!(trace, expected);
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(&data, 42));
+}
+
+#[test]
+fn test_finish_bytes_be() {
+    use core::convert::TryInto;
+
+    for (seed, _, h64_1, h64_2, s) in DATA {
+        let mut hasher = Hasher128::with_seed(*seed);
+        hasher.write_str(s);
+        let le = hasher.finish_bytes();
+        let be = hasher.finish_bytes_be();
+        let mut expected = le;
+        expected[..8].reverse();
+        expected[8..].reverse();
+        assert_eq!(be, expected, "{}", s);
+        assert_eq!(u64::from_be_bytes(be[..8].try_into().unwrap()), *h64_1);
+        assert_eq!(u64::from_be_bytes(be[8..].try_into().unwrap()), *h64_2);
+        assert_eq!(murmurhash3_x64_128_bytes_be(s.as_bytes(), *seed), be);
+    }
+}
+
+#[test]
+fn test_murmurhash3_x64_128_vectored() {
+    let data: Vec<u8> = (0..100).collect();
+    let expected = murmurhash3_x64_128(&data, 42);
+    let layouts: &[&[usize]] = &[&[], &[0], &[15], &[16, 16], &[1, 2, 3, 50], &[99]];
+    for splits in layouts {
+        let mut bufs = vec![];
+        let mut rest = &data[..];
+        for &n in *splits {
+            let (head, tail) = rest.split_at(n);
+            bufs.push(head);
+            rest = tail;
+        }
+        bufs.push(rest);
+        assert_eq!(
+            murmurhash3_x64_128_vectored(&bufs, 42),
+            expected,
+            "{:?}",
+            splits
+        );
+        #[cfg(feature = "std")]
+        {
+            let slices: Vec<_> = bufs.iter().map(|b| std::io::IoSlice::new(b)).collect();
+            assert_eq!(murmurhash3_x64_128_io_slices(&slices, 42), expected);
+        }
+    }
+    assert_eq!(
+        murmurhash3_x64_128_vectored(&[], 42),
+        murmurhash3_x64_128(b"", 42)
+    );
+}
+
+#[test]
+fn test_peek128() {
+    let mut hasher = Hasher128::with_seed(42);
+    assert_eq!(hasher.peek128(), murmurhash3_x64_128(b"", 42));
+    hasher.write(b"hello");
+    let h = murmurhash3_x64_128(b"hello", 42);
+    assert_eq!(hasher.peek128(), h);
+    assert_eq!(hasher.peek128(), h);
+    assert_eq!(hasher.finish128(), h);
+
+    // Writing after a peek invalidates the cached result, through any of
+    // the ways to feed bytes.
+    hasher.write(b" world");
+    let h = murmurhash3_x64_128(b"hello world", 42);
+    assert_eq!(hasher.finish128(), h);
+    assert_eq!(hasher.peek128(), h);
+    hasher.write_u8(b'!');
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(b"hello world!", 42));
+    assert_eq!(hasher.peek128(), murmurhash3_x64_128(b"hello world!", 42));
+    hasher.write(&[b'?'; 4]);
+    hasher.feed_block(7, 8);
+    let mut data = b"hello world!????".to_vec();
+    data.extend_from_slice(&7u64.to_le_bytes());
+    data.extend_from_slice(&8u64.to_le_bytes());
+    assert_eq!(hasher.peek128(), murmurhash3_x64_128(&data, 42));
+
+    // A cached result is not shared with a restarted hasher.
+    hasher.reset_with_seed(1);
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(b"", 1));
+}
+
+// Moving a hasher with the cache into a closure by value produced wrong
+// sums with rustc 1.95 at opt-level 2 and above when the cache was keyed
+// by the written length, run with `--release` to cover it.
+#[test]
+fn test_peek128_moved() {
+    let finish = |mut hasher: Hasher128| {
+        hasher.write(b"hello");
+        hasher.finish128()
+    };
+    let peek = |mut hasher: Hasher128| {
+        hasher.write(b"he");
+        hasher.peek128();
+        hasher.write(b"llo");
+        hasher.peek128()
+    };
+    let expected = murmurhash3_x64_128(b"hello", 42);
+    assert_eq!(finish(Hasher128::with_seed(42)), expected);
+    assert_eq!(
+        finish(Hasher128::with_seed_bytes(42u32.to_le_bytes())),
+        expected
+    );
+    assert_eq!(
+        peek(Hasher128::with_seed_bytes(42u32.to_le_bytes())),
+        expected
+    );
+    let mut hasher = Hasher128::with_seed_bytes(42u32.to_le_bytes());
+    hasher.write(b"hello");
+    hasher.peek128();
+    let finish_peeked = move || hasher.finish128();
+    assert_eq!(finish_peeked(), expected);
+}
+
+#[test]
+fn test_murmur128_sink() {
+    let data: Vec<u8> = (0..200).collect();
+    let expected = murmurhash3_x64_128(&data, 42);
+    for size in [1, 7, 16, 33, 200] {
+        let mut sink = Murmur128Sink::new(42);
+        for chunk in data.chunks(size) {
+            sink.absorb(chunk);
+        }
+        assert_eq!(sink.digest(), expected, "{}", size);
+        assert_eq!(sink.into_inner().finish128(), expected);
+
+        let mut sink = Murmur128Sink::from(Hasher128::with_seed(42));
+        sink.extend(data.chunks(size));
+        assert_eq!(sink.digest(), expected, "{}", size);
+    }
+}
+
+#[test]
+fn test_with_mixed_seed() {
+    // Short keys of zeros are the worst case, their blocks don't change
+    // the state.
+    let hashes = |new: fn(u32) -> Hasher128| {
+        let mut res = vec![];
+        for len in 0..=8 {
+            for seed in 0..16 {
+                let mut hasher = new(seed);
+                hasher.write(&vec![0; len]);
+                res.push(hasher.finish128());
+            }
+        }
+        res
+    };
+    // Returns the minimum and the mean deviation from 64 of the count of
+    // different bits between every pair of hashes.
+    let stats = |hashes: &[(u64, u64)]| {
+        let (mut min, mut dev, mut n) = (128, 0.0, 0);
+        for (i, a) in hashes.iter().enumerate() {
+            for b in &hashes[i + 1..] {
+                let d = (a.0 ^ b.0).count_ones() + (a.1 ^ b.1).count_ones();
+                min = min.min(d);
+                dev += (d as f64 - 64.0).abs();
+                n += 1;
+            }
+        }
+        (min, dev / n as f64)
+    };
+
+    let (raw_min, raw_dev) = stats(&hashes(Hasher128::with_seed));
+    let (mixed_min, mixed_dev) = stats(&hashes(Hasher128::with_mixed_seed));
+    // Raw seeds collide, for example, `[]` with seed 1 and `[0]` with seed 0.
+    assert_eq!(raw_min, 0);
+    assert!(mixed_min > 32, "{}", mixed_min);
+    assert!(mixed_dev < raw_dev, "{} {}", mixed_dev, raw_dev);
+
+    assert_ne!(
+        Hasher128::with_mixed_seed(0).finish128(),
+        Hasher128::with_seed(0).finish128()
+    );
+}
+
+#[cfg(all(unix, feature = "std"))]
+#[test]
+fn test_murmurhash3_x64_128_path() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    for p in ["", "/", "/tmp/a.txt", "relative/path/to/some/file.rs"] {
+        assert_eq!(
+            murmurhash3_x64_128_path(Path::new(p), 42),
+            murmurhash3_x64_128(p.as_bytes(), 42)
+        );
+    }
+    // Paths on Unix are not necessarily UTF-8.
+    let raw = b"/tmp/\xff\xfe";
+    let p = Path::new(OsStr::from_bytes(raw));
+    assert_eq!(
+        murmurhash3_x64_128_path(p, 42),
+        murmurhash3_x64_128(raw, 42)
+    );
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "hasher is not block aligned")]
+fn test_feed_block_unaligned() {
+    let mut hasher = Hasher128::with_seed(0);
+    hasher.write(b"abc");
+    hasher.feed_block(1, 2);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "tail of 16 bytes is not shorter than a block")]
+fn test_finish_tail128_long_tail() {
+    mur3::raw::finish_tail128(&[0; 16], 16, 0, 0);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "tail of 4 bytes is not shorter than a block")]
+fn test_finish_tail32_long_tail() {
+    mur3::raw::finish_tail32(&[0; 4], 4, 0);
+}
+
+#[test]
+fn test_restore_tagged() {
+    use std::convert::TryFrom;
+
+    let mut h128 = Hasher128::with_seed(42);
+    h128.write(b"hello world, 128");
+    h128.write(b"!!");
+    let mut h32 = Hasher32::with_seed(42);
+    h32.write(b"hello world");
+
+    let blob128 = h128.snapshot_tagged();
+    let blob32 = h32.snapshot_tagged();
+    assert_eq!(restore(&blob128), Ok(HasherKind::Hasher128(h128.clone())));
+    assert_eq!(
+        HasherKind::try_from(&blob32[..]),
+        Ok(HasherKind::Hasher32(h32.clone()))
+    );
+    assert_eq!(
+        format!("{:?}", Hasher32::with_seed(0)),
+        "Hasher32 { h: 0, buf: [], consume: 0 }"
+    );
+
+    assert_eq!(restore(&[]).err(), Some(RestoreError::TooShort));
+    assert_eq!(restore(&blob128[..41]).err(), Some(RestoreError::TooShort));
+    assert_eq!(restore(&blob32[..17]).err(), Some(RestoreError::TooShort));
+    let mut bad = blob128;
+    bad[0] = 2;
+    assert_eq!(restore(&bad).err(), Some(RestoreError::BadVersion));
+    let mut bad = blob128;
+    bad[1] = 64;
+    assert_eq!(restore(&bad).err(), Some(RestoreError::BadKind));
+    let mut bad = blob128;
+    bad[41] = 16;
+    assert_eq!(restore(&bad).err(), Some(RestoreError::BadLen));
+    let mut bad = blob32;
+    bad[17] = 4;
+    assert_eq!(restore(&bad).err(), Some(RestoreError::BadLen));
+    // A Hasher32 blob is never taken as a Hasher128.
+    let mut bad = blob32.to_vec();
+    bad[1] = blob128[1];
+    assert_eq!(restore(&bad).err(), Some(RestoreError::TooShort));
+}
+
+#[test]
+fn test_write_report() {
+    let data: Vec<u8> = (0..64).collect();
+    let mut hasher = Hasher128::with_seed(42);
+    let report = hasher.write_report(&data[..37]);
+    assert_eq!(report.blocks_processed, 2);
+    assert_eq!(report.tail_buffered, 5);
+
+    // The buffered bytes complete a block with the next write.
+    let report = hasher.write_report(&data[37..50]);
+    assert_eq!(report.blocks_processed, 1);
+    assert_eq!(report.tail_buffered, 2);
+    let report = hasher.write_report(&data[50..51]);
+    assert_eq!(report.blocks_processed, 0);
+    assert_eq!(report.tail_buffered, 3);
+    hasher.write(&data[51..]);
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(&data, 42));
+}
+
+#[test]
+fn test_build_hasher_maps() {
+    use core::hash::BuildHasher;
+
+    let build = Murmur3BuildHasher128::with_seed(42);
+    assert_eq!(build.seed(), 42);
+    let mut hasher = build.build_hasher();
+    hasher.write(b"hello");
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(b"hello", 42));
+
+    let mut map = hashbrown::HashMap::with_hasher(build);
+    let mut index = indexmap::IndexMap::with_hasher(build);
+    for i in 0..100 {
+        map.insert(format!("key{}", i), i);
+        index.insert(format!("key{}", i), i);
+    }
+    for i in 0..100 {
+        assert_eq!(map.get(&format!("key{}", i)), Some(&i));
+        assert_eq!(index.get(&format!("key{}", i)), Some(&i));
+    }
+    assert_eq!(index.get_index(7), Some((&"key7".to_string(), &7)));
+
+    // Maps created by `Default` use seed 0.
+    let mut map: hashbrown::HashMap<u64, u64, Murmur3BuildHasher128> = Default::default();
+    map.insert(1, 2);
+    assert_eq!(map[&1], 2);
+    let mut index: indexmap::IndexMap<u64, u64, Murmur3BuildHasher128> = Default::default();
+    index.insert(1, 2);
+    assert_eq!(index[&1], 2);
+}
+
+#[test]
+fn test_backend_detect() {
+    use mur3::backend::{self, Backend};
+
+    let detected = backend::detect();
+    assert!(!detected.name().is_empty());
+    // The cached result is the same backend.
+    assert_eq!(backend::detect().name(), detected.name());
+    for (seed, _, h64_1, h64_2, s) in DATA {
+        assert_eq!(detected.hash128(s.as_bytes(), *seed), (*h64_1, *h64_2));
+        assert_eq!(
+            backend::Scalar.hash128(s.as_bytes(), *seed),
+            (*h64_1, *h64_2)
+        );
+    }
+    let data: Vec<u8> = (0..100).collect();
+    assert_eq!(
+        detected.hash128_blocks(&data, 1, 2),
+        backend::Scalar.hash128_blocks(&data, 1, 2)
+    );
+}
+
+#[test]
+fn test_const_with_seed() {
+    const HASHER128: Hasher128 = Hasher128::with_seed(42);
+    const HASHER32: Hasher32 = Hasher32::with_seed(42);
+    static TABLE: [Hasher128; 2] = [Hasher128::with_seed(1), Hasher128::with_seed(2)];
+
+    let mut hasher = HASHER128.clone();
+    hasher.write(b"hello");
+    assert_eq!(hasher.finish128(), murmurhash3_x64_128(b"hello", 42));
+    let mut hasher = HASHER32.clone();
+    hasher.write(b"hello");
+    assert_eq!(hasher.finish32(), murmurhash3_x86_32(b"hello", 42));
+    for (i, h) in TABLE.iter().enumerate() {
+        let mut hasher = h.clone();
+        hasher.write(b"hello");
+        assert_eq!(
+            hasher.finish128(),
+            murmurhash3_x64_128(b"hello", i as u32 + 1)
+        );
+    }
+}
+
+// Too slow under Miri, and it has no unsafe code paths of its own.
+#[test]
+#[cfg_attr(miri, ignore)]
+fn test_hash_combine32() {
+    use std::collections::HashSet;
+
+    for (seed, value) in [(0, 0), (1, 2), (42, 0xdeadbeef), (u32::MAX, 7)] {
+        let h = hash_combine32(seed, value);
+        assert_eq!(h, hash_combine32(seed, value));
+        assert_eq!(h, murmurhash3_x86_32(&value.to_le_bytes(), seed));
+    }
+
+    // 65536 distinct pairs, a perfect 32-bit hash would expect about 0.5
+    // collisions.
+    let mut seen = HashSet::new();
+    let mut collisions = 0;
+    for seed in 0..256 {
+        for value in 0..256 {
+            if !seen.insert(hash_combine32(seed, value)) {
+                collisions += 1;
+            }
+        }
+    }
+    assert!(collisions <= 4, "{}", collisions);
+}
+
+#[test]
+fn test_write_u64_pair() {
+    let words = [
+        (0, 0),
+        (1, 2),
+        (0x0123456789abcdef, 0xfedcba9876543210),
+        (u64::MAX, 7),
+    ];
+    let mut hasher = Hasher128::with_seed(42);
+    let mut expected = Hasher128::with_seed(42);
+    for &(a, b) in &words {
+        hasher.write_u64_pair(a, b);
+        expected.write(&[a.to_le_bytes(), b.to_le_bytes()].concat());
+        assert_eq!(hasher.finish128(), expected.finish128());
+    }
+    hasher.write(b"tail");
+    expected.write(b"tail");
+    assert_eq!(hasher.finish128(), expected.finish128());
+}
+
+#[test]
+fn test_block_remaining() {
+    let mut hasher = Hasher128::with_seed(0);
+    assert_eq!(hasher.block_remaining(), 16);
+    hasher.write(b"hello");
+    assert_eq!(hasher.block_remaining(), 11);
+    hasher.write(&[0; 11]);
+    assert_eq!(hasher.block_remaining(), 16);
+    hasher.write(&[0; 20]);
+    assert_eq!(hasher.block_remaining(), 12);
+
+    let mut hasher = Hasher32::with_seed(0);
+    assert_eq!(hasher.block_remaining(), 4);
+    hasher.write(b"hello");
+    assert_eq!(hasher.block_remaining(), 3);
+    hasher.write(&[0; 3]);
+    assert_eq!(hasher.block_remaining(), 4);
+}
+
+#[test]
+fn test_finish128_with_len() {
+    let mut hasher = Hasher128::with_seed(42);
+    hasher.write(b"hello world, hello world");
+    let len = hasher.bytes_written();
+    assert_eq!(len, 24);
+    assert_eq!(hasher.finish128_with_len(len), hasher.finish128());
+    assert_ne!(hasher.finish128_with_len(len + 1), hasher.finish128());
+    assert_ne!(hasher.finish128_with_len(0), hasher.finish128());
+}
+
+#[test]
+fn test_murmurhash3_x64_128_ints() {
+    let i32s: Vec<i32> = (-50..50).map(|i| i * 0x01020304).collect();
+    let bytes: Vec<u8> = i32s.iter().flat_map(|i| i.to_le_bytes()).collect();
+    assert_eq!(
+        murmurhash3_x64_128_ints(&i32s, 42),
+        murmurhash3_x64_128(&bytes, 42)
+    );
+
+    let u64s: Vec<u64> = (0..37).map(|i| i * 0x0102030405060708).collect();
+    let bytes: Vec<u8> = u64s.iter().flat_map(|i| i.to_le_bytes()).collect();
+    assert_eq!(
+        murmurhash3_x64_128_ints(&u64s, 42),
+        murmurhash3_x64_128(&bytes, 42)
+    );
+
+    let i128s = [i128::MIN, -1, 0, 1, i128::MAX];
+    let bytes: Vec<u8> = i128s.iter().flat_map(|i| i.to_le_bytes()).collect();
+    assert_eq!(
+        murmurhash3_x64_128_ints(&i128s, 42),
+        murmurhash3_x64_128(&bytes, 42)
+    );
+
+    let empty: [u16; 0] = [];
+    assert_eq!(
+        murmurhash3_x64_128_ints(&empty, 42),
+        murmurhash3_x64_128(b"", 42)
+    );
 }